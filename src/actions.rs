@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+
+use bevy::input::InputSystem;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+const ROTATE_HAND_UP_KEY: KeyCode = KeyCode::W;
+const ROTATE_HAND_DOWN_KEY: KeyCode = KeyCode::S;
+const ROTATE_ARM_UP_KEY: KeyCode = KeyCode::Up;
+const ROTATE_ARM_DOWN_KEY: KeyCode = KeyCode::Down;
+const EXTEND_ARM_KEY: KeyCode = KeyCode::Left;
+const RETRACT_ARM_KEY: KeyCode = KeyCode::Right;
+const PRESS_KEY: KeyCode = KeyCode::Space;
+const RESET_ARM_KEY: KeyCode = KeyCode::R;
+
+pub struct ActionPlugin;
+
+impl Plugin for ActionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ActionState::default())
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                action_state_reset_system
+                    .label("action_state_reset")
+                    .after(InputSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                keyboard_action_system
+                    .label("actions")
+                    .after("action_state_reset"),
+            );
+    }
+}
+
+/// Something the player can do, regardless of what device they're doing it with
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum InputAction {
+    RotateHandUp,
+    RotateHandDown,
+    RotateArmUp,
+    RotateArmDown,
+    ExtendArm,
+    RetractArm,
+    Press,
+    ResetArm,
+}
+
+/// Which actions are being done this frame. Each input device adds to this every frame, so gameplay systems don't need to care where an action came from.
+#[derive(Default)]
+pub struct ActionState {
+    pressed: HashSet<InputAction>,
+    previously_pressed: HashSet<InputAction>,
+}
+
+impl ActionState {
+    /// Marks the provided action as being done this frame
+    pub fn press(&mut self, action: InputAction) {
+        self.pressed.insert(action);
+    }
+
+    /// Whether the provided action is being done
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    /// Whether any action at all is being done
+    pub fn any_pressed(&self) -> bool {
+        !self.pressed.is_empty()
+    }
+
+    /// Whether the provided action started being done this frame
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.pressed(action) && !self.previously_pressed.contains(&action)
+    }
+
+    /// Starts a new frame, with nothing being done yet
+    fn reset(&mut self) {
+        self.previously_pressed = std::mem::take(&mut self.pressed);
+    }
+}
+
+/// The keys used to control the hand and arm
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub rotate_hand_up: KeyCode,
+    pub rotate_hand_down: KeyCode,
+    pub rotate_arm_up: KeyCode,
+    pub rotate_arm_down: KeyCode,
+    pub extend_arm: KeyCode,
+    pub retract_arm: KeyCode,
+    pub press: KeyCode,
+    pub reset_arm: KeyCode,
+    /// Whether the hand rotation keys turn the hand the other way
+    pub invert_hand: bool,
+    /// Whether the arm rotation keys turn the arm the other way
+    pub invert_arm: bool,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            rotate_hand_up: ROTATE_HAND_UP_KEY,
+            rotate_hand_down: ROTATE_HAND_DOWN_KEY,
+            rotate_arm_up: ROTATE_ARM_UP_KEY,
+            rotate_arm_down: ROTATE_ARM_DOWN_KEY,
+            extend_arm: EXTEND_ARM_KEY,
+            retract_arm: RETRACT_ARM_KEY,
+            press: PRESS_KEY,
+            reset_arm: RESET_ARM_KEY,
+            invert_hand: false,
+            invert_arm: false,
+        }
+    }
+}
+
+impl KeyBindings {
+    /// The key bound to the provided action
+    pub fn key(&self, action: InputAction) -> KeyCode {
+        match action {
+            InputAction::RotateHandUp => self.rotate_hand_up,
+            InputAction::RotateHandDown => self.rotate_hand_down,
+            InputAction::RotateArmUp => self.rotate_arm_up,
+            InputAction::RotateArmDown => self.rotate_arm_down,
+            InputAction::ExtendArm => self.extend_arm,
+            InputAction::RetractArm => self.retract_arm,
+            InputAction::Press => self.press,
+            InputAction::ResetArm => self.reset_arm,
+        }
+    }
+
+    /// Binds the provided key to the provided action
+    pub fn set_key(&mut self, action: InputAction, key: KeyCode) {
+        let binding = match action {
+            InputAction::RotateHandUp => &mut self.rotate_hand_up,
+            InputAction::RotateHandDown => &mut self.rotate_hand_down,
+            InputAction::RotateArmUp => &mut self.rotate_arm_up,
+            InputAction::RotateArmDown => &mut self.rotate_arm_down,
+            InputAction::ExtendArm => &mut self.extend_arm,
+            InputAction::RetractArm => &mut self.retract_arm,
+            InputAction::Press => &mut self.press,
+            InputAction::ResetArm => &mut self.reset_arm,
+        };
+        *binding = key;
+    }
+
+    /// The action the provided key is bound to, if any
+    pub fn action_for(&self, key: KeyCode) -> Option<InputAction> {
+        ALL_ACTIONS
+            .into_iter()
+            .find(|action| self.key(*action) == key)
+    }
+}
+
+/// Every action, for going through all of them
+pub const ALL_ACTIONS: [InputAction; 8] = [
+    InputAction::RotateHandUp,
+    InputAction::RotateHandDown,
+    InputAction::RotateArmUp,
+    InputAction::RotateArmDown,
+    InputAction::ExtendArm,
+    InputAction::RetractArm,
+    InputAction::Press,
+    InputAction::ResetArm,
+];
+
+/// Handles clearing out last frame's actions before the input devices fill in this frame's
+fn action_state_reset_system(mut action_state: ResMut<ActionState>) {
+    action_state.reset();
+}
+
+/// Handles turning held keys into actions
+fn keyboard_action_system(
+    keyboard: Res<Input<KeyCode>>,
+    save_data: Res<SaveData>,
+    mut action_state: ResMut<ActionState>,
+) {
+    let key_bindings = &save_data.settings.key_bindings;
+    for action in ALL_ACTIONS {
+        if keyboard.pressed(key_bindings.key(action)) {
+            action_state.press(action);
+        }
+    }
+}
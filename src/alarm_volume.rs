@@ -0,0 +1,136 @@
+use bevy_kira_audio::Audio;
+
+use crate::*;
+
+/// How quiet the alarm gets while a sound effect is playing, relative to its normal volume
+const DUCKED_VOLUME: f32 = 0.35;
+/// How long the alarm stays fully ducked after a sound effect starts
+const DUCK_HOLD_SECONDS: f32 = 0.25;
+/// How long it takes the alarm to come back up to its normal volume after being ducked
+const DUCK_RELEASE_SECONDS: f32 = 0.4;
+/// How much louder the alarm gets at full tension, as a fraction of its normal volume
+const TENSION_EXTRA_VOLUME: f32 = 0.3;
+/// How much louder the alarm gets once the player has ignored it for long enough, as a fraction of its normal volume
+const IDLE_EXTRA_VOLUME: f32 = 0.5;
+
+pub struct AlarmVolumePlugin;
+
+impl Plugin for AlarmVolumePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DuckTimer(None))
+            .insert_resource(AlarmFade(None))
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_run_criteria(game_running)
+                    .with_system(alarm_fade_system.label("alarm_fade"))
+                    .with_system(alarm_volume_system.after("alarm_fade")),
+            );
+    }
+}
+
+/// Tracks how long ago the alarm was ducked, if it's currently ducked
+pub struct DuckTimer(Option<Timer>);
+
+impl DuckTimer {
+    /// Lowers the alarm so a sound effect that just started playing can be heard over it
+    pub fn duck(&mut self) {
+        self.0 = Some(Timer::from_seconds(
+            DUCK_HOLD_SECONDS + DUCK_RELEASE_SECONDS,
+            false,
+        ));
+    }
+
+    /// How much the alarm volume is currently scaled down by ducking
+    fn volume_scale(&self) -> f32 {
+        let timer = match &self.0 {
+            Some(timer) => timer,
+            None => return 1.0,
+        };
+
+        let release_progress =
+            ((timer.elapsed_secs() - DUCK_HOLD_SECONDS) / DUCK_RELEASE_SECONDS).clamp(0.0, 1.0);
+        DUCKED_VOLUME + (1.0 - DUCKED_VOLUME) * release_progress
+    }
+}
+
+/// Tracks the alarm fading in or out along with the screen, if it's currently fading
+struct AlarmFade(Option<(FadeDirection, Timer)>);
+
+impl AlarmFade {
+    /// How much the alarm volume is currently scaled down by fading
+    fn volume_scale(&self) -> f32 {
+        match &self.0 {
+            Some((FadeDirection::In, timer)) => timer.percent(),
+            Some((FadeDirection::Out, timer)) => timer.percent_left(),
+            None => 1.0,
+        }
+    }
+}
+
+/// The volume the alarm plays at when it isn't ducked or fading
+pub fn alarm_volume(settings: &Settings) -> f32 {
+    settings.master_volume * settings.alarm_volume
+}
+
+/// Handles starting the alarm fading whenever the screen does, so they stay in sync
+fn alarm_fade_system(mut alarm_fade: ResMut<AlarmFade>, mut fade_events: EventReader<FadeEvent>) {
+    for event in fade_events.iter() {
+        alarm_fade.0 = Some(match event.0 {
+            FadeDirection::In => (FadeDirection::In, Timer::new(FADE_IN_TIME, false)),
+            FadeDirection::Out => (FadeDirection::Out, Timer::new(FADE_OUT_TIME, false)),
+        });
+    }
+}
+
+/// Handles setting the alarm volume, taking into account the volume settings, fading, ducking for sound effects, how close the wake goal is, and how long the player has been ignoring it
+fn alarm_volume_system(
+    time: Res<Time>,
+    audio: Res<Audio>,
+    save_data: Res<SaveData>,
+    alarm_active: Res<AlarmActive>,
+    alarm_channel: Res<AlarmChannel>,
+    tension: Res<Tension>,
+    idle: Res<Idle>,
+    mut alarm_fade: ResMut<AlarmFade>,
+    mut duck_timer: ResMut<DuckTimer>,
+    mut last_idle_escalation: Local<f32>,
+) {
+    // idle time goes up every frame, so only the escalation it causes matters
+    let idle_escalation = idle.escalation();
+    let escalating = idle_escalation != *last_idle_escalation;
+    *last_idle_escalation = idle_escalation;
+
+    let changing = alarm_fade.0.is_some() || duck_timer.0.is_some() || escalating;
+    if !changing && !alarm_active.is_changed() && !save_data.is_changed() && !tension.is_changed() {
+        // nothing new to apply
+        return;
+    }
+
+    let mut fade_finished = false;
+    if let Some((_, timer)) = alarm_fade.0.as_mut() {
+        fade_finished = timer.tick(time.delta()).finished();
+    }
+
+    let mut duck_finished = false;
+    if let Some(timer) = duck_timer.0.as_mut() {
+        duck_finished = timer.tick(time.delta()).finished();
+    }
+
+    let volume = alarm_volume(&save_data.settings)
+        * alarm_fade.volume_scale()
+        * duck_timer.volume_scale()
+        * (1.0 + tension.0 * TENSION_EXTRA_VOLUME)
+        * (1.0 + idle_escalation * IDLE_EXTRA_VOLUME);
+    audio.set_volume_in_channel(volume, &alarm_channel.0);
+
+    if fade_finished {
+        if let Some((FadeDirection::In, _)) = alarm_fade.0 {
+            // all the way back up
+            alarm_fade.0 = None;
+        }
+        // a finished fade out stays silent until the next fade in starts
+    }
+    if duck_finished {
+        duck_timer.0 = None;
+    }
+}
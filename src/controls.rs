@@ -0,0 +1,447 @@
+use crate::*;
+
+/// Stops waiting for a key without changing anything
+const CANCEL_REBIND_KEY: KeyCode = KeyCode::Escape;
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Rebinding(None))
+            .add_system_set(SystemSet::on_enter(GameState::Controls).with_system(controls_setup))
+            .add_system_set(
+                SystemSet::on_update(GameState::Controls)
+                    .with_system(binding_button_system.label("binding_button"))
+                    .with_system(invert_button_system)
+                    .with_system(rebind_system.after("binding_button")),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Controls)
+                    .with_system(despawn_components_system::<ControlsComponent>)
+                    .with_system(stop_rebinding),
+            )
+            .add_system(reset_bindings_button_system)
+            .add_system(controls_back_button_system);
+    }
+}
+
+#[derive(Component)]
+struct ControlsComponent;
+
+#[derive(Component)]
+struct ControlsBackButton;
+
+#[derive(Component)]
+struct ResetBindingsButton;
+
+/// A button for changing which key is bound to an action
+#[derive(Component)]
+struct BindingButton(InputAction);
+
+/// A rotation control that can be turned around
+#[derive(Clone, Copy)]
+enum InvertSetting {
+    Hand,
+    Arm,
+}
+
+impl InvertSetting {
+    const ALL: [InvertSetting; 2] = [InvertSetting::Hand, InvertSetting::Arm];
+
+    /// Whether this is inverted, out of the provided bindings
+    fn inverted_mut<'a>(&self, key_bindings: &'a mut KeyBindings) -> &'a mut bool {
+        match self {
+            InvertSetting::Hand => &mut key_bindings.invert_hand,
+            InvertSetting::Arm => &mut key_bindings.invert_arm,
+        }
+    }
+
+    /// The label for the button for this, out of the provided bindings
+    fn label(&self, key_bindings: &KeyBindings, localization: &Localization) -> String {
+        let (name_key, inverted) = match self {
+            InvertSetting::Hand => ("controls.invert_hand", key_bindings.invert_hand),
+            InvertSetting::Arm => ("controls.invert_arm", key_bindings.invert_arm),
+        };
+        toggle_label(name_key, inverted, localization)
+    }
+}
+
+/// A button for turning a rotation control around
+#[derive(Component)]
+struct InvertButton(InvertSetting);
+
+/// Shows why the last attempt at changing a binding didn't work
+#[derive(Component)]
+struct RebindMessageText;
+
+/// The action waiting for the player to press the key they want for it, if any
+struct Rebinding(Option<InputAction>);
+
+/// The name of the provided action, for showing to the player
+fn action_name(action: InputAction, localization: &Localization) -> String {
+    let key = match action {
+        InputAction::RotateHandUp => "controls.rotate_hand_up",
+        InputAction::RotateHandDown => "controls.rotate_hand_down",
+        InputAction::RotateArmUp => "controls.rotate_arm_up",
+        InputAction::RotateArmDown => "controls.rotate_arm_down",
+        InputAction::ExtendArm => "controls.extend_arm",
+        InputAction::RetractArm => "controls.retract_arm",
+        InputAction::Press => "controls.press",
+        InputAction::ResetArm => "controls.reset_arm",
+    };
+    localization.localize(key).to_string()
+}
+
+/// The label for the button for the provided action
+fn binding_label(
+    action: InputAction,
+    key_bindings: &KeyBindings,
+    rebinding: &Rebinding,
+    localization: &Localization,
+) -> String {
+    let name = action_name(action, localization);
+    if rebinding.0 == Some(action) {
+        localization.localize_with("controls.waiting", &[("action", name)])
+    } else {
+        localization.localize_with(
+            "controls.binding",
+            &[
+                ("action", name),
+                ("key", format!("{:?}", key_bindings.key(action))),
+            ],
+        )
+    }
+}
+
+/// Spawns a button on the controls screen with the provided label
+fn spawn_controls_button<T: Component>(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    ui_scale: f32,
+    label: String,
+    marker: T,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(550.0 * ui_scale), Val::Px(40.0 * ui_scale)),
+                margin: Rect::all(Val::Px(4.0 * ui_scale)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .insert(marker)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    label,
+                    TextStyle {
+                        font,
+                        font_size: 22.0 * ui_scale,
+                        color: Color::SEA_GREEN,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                ..Default::default()
+            });
+        });
+}
+
+/// Sets up the controls screen.
+fn controls_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+    rebinding: Res<Rebinding>,
+) {
+    let font = asset_server.load(MAIN_FONT);
+    let ui_scale = save_data.settings.ui_scale();
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .insert(ControlsComponent)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    localization.localize("controls.title"),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 70.0 * ui_scale,
+                        color: Color::WHITE,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                style: Style {
+                    margin: Rect::all(Val::Px(15.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            let key_bindings = &save_data.settings.key_bindings;
+            for action in ALL_ACTIONS {
+                spawn_controls_button(
+                    parent,
+                    font.clone(),
+                    ui_scale,
+                    binding_label(action, key_bindings, &rebinding, &localization),
+                    BindingButton(action),
+                );
+            }
+            for setting in InvertSetting::ALL {
+                spawn_controls_button(
+                    parent,
+                    font.clone(),
+                    ui_scale,
+                    setting.label(key_bindings, &localization),
+                    InvertButton(setting),
+                );
+            }
+
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 20.0 * ui_scale,
+                            color: Color::GRAY,
+                        },
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        },
+                    ),
+                    style: Style {
+                        margin: Rect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(RebindMessageText);
+
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("controls.defaults"),
+                ResetBindingsButton,
+            );
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("common.back"),
+                ControlsBackButton,
+            );
+        });
+}
+
+/// Updates the labels on all the binding buttons
+fn update_binding_labels(
+    key_bindings: &KeyBindings,
+    rebinding: &Rebinding,
+    localization: &Localization,
+    button_query: &Query<(&BindingButton, &Children)>,
+    text_query: &mut Query<&mut Text, Without<RebindMessageText>>,
+) {
+    for (button, children) in button_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value =
+                    binding_label(button.0, key_bindings, rebinding, localization);
+            }
+        }
+    }
+}
+
+/// Updates the labels on all the invert buttons
+fn update_invert_labels(
+    key_bindings: &KeyBindings,
+    localization: &Localization,
+    invert_query: &Query<(&InvertButton, &Children)>,
+    text_query: &mut Query<&mut Text, Without<RebindMessageText>>,
+) {
+    for (button, children) in invert_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = button.0.label(key_bindings, localization);
+            }
+        }
+    }
+}
+
+/// Handles turning rotation controls around
+fn invert_button_system(
+    mut save_data: ResMut<SaveData>,
+    localization: Res<Localization>,
+    interaction_query: Query<(&Interaction, &InvertButton), Changed<Interaction>>,
+    invert_query: Query<(&InvertButton, &Children)>,
+    mut text_query: Query<&mut Text, Without<RebindMessageText>>,
+) {
+    let mut changed = false;
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            let inverted = button.0.inverted_mut(&mut save_data.settings.key_bindings);
+            *inverted = !*inverted;
+            changed = true;
+        }
+    }
+
+    if changed {
+        save_data.save();
+        update_invert_labels(
+            &save_data.settings.key_bindings,
+            &localization,
+            &invert_query,
+            &mut text_query,
+        );
+    }
+}
+
+/// Handles clicking on a binding to start waiting for the key to change it to
+fn binding_button_system(
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+    mut rebinding: ResMut<Rebinding>,
+    interaction_query: Query<(&Interaction, &BindingButton), Changed<Interaction>>,
+    button_query: Query<(&BindingButton, &Children)>,
+    mut text_query: Query<&mut Text, Without<RebindMessageText>>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Clicked);
+    if let Some((_, button)) = clicked {
+        rebinding.0 = Some(button.0);
+        update_binding_labels(
+            &save_data.settings.key_bindings,
+            &rebinding,
+            &localization,
+            &button_query,
+            &mut text_query,
+        );
+    }
+}
+
+/// Handles binding the next key pressed to the action waiting for one. Keys that are already bound to something else are turned away.
+fn rebind_system(
+    keyboard: Res<Input<KeyCode>>,
+    mut save_data: ResMut<SaveData>,
+    localization: Res<Localization>,
+    mut rebinding: ResMut<Rebinding>,
+    button_query: Query<(&BindingButton, &Children)>,
+    mut text_query: Query<&mut Text, Without<RebindMessageText>>,
+    mut message_query: Query<&mut Text, With<RebindMessageText>>,
+) {
+    let action = match rebinding.0 {
+        Some(action) => action,
+        None => return,
+    };
+    if rebinding.is_changed() {
+        // just started waiting, so a key that clicked the button with the keyboard shouldn't count
+        return;
+    }
+    let key = match keyboard.get_just_pressed().next() {
+        Some(key) => *key,
+        None => return,
+    };
+
+    let message = if key == CANCEL_REBIND_KEY {
+        String::new()
+    } else {
+        match save_data.settings.key_bindings.action_for(key) {
+            Some(bound_action) if bound_action != action => localization.localize_with(
+                "controls.already_used",
+                &[
+                    ("key", format!("{key:?}")),
+                    ("action", action_name(bound_action, &localization)),
+                ],
+            ),
+            _ => {
+                save_data.settings.key_bindings.set_key(action, key);
+                save_data.save();
+                String::new()
+            }
+        }
+    };
+
+    rebinding.0 = None;
+    update_binding_labels(
+        &save_data.settings.key_bindings,
+        &rebinding,
+        &localization,
+        &button_query,
+        &mut text_query,
+    );
+    for mut text in message_query.iter_mut() {
+        text.sections[0].value = message.clone();
+    }
+}
+
+/// Stops waiting for a key when leaving the controls screen
+fn stop_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+}
+
+/// Handles interactions with the button for putting all the bindings back to how they started out.
+fn reset_bindings_button_system(
+    mut save_data: ResMut<SaveData>,
+    localization: Res<Localization>,
+    rebinding: Res<Rebinding>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ResetBindingsButton>)>,
+    button_query: Query<(&BindingButton, &Children)>,
+    invert_query: Query<(&InvertButton, &Children)>,
+    mut text_query: Query<&mut Text, Without<RebindMessageText>>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            save_data.settings.key_bindings = KeyBindings::default();
+            save_data.save();
+            update_binding_labels(
+                &save_data.settings.key_bindings,
+                &rebinding,
+                &localization,
+                &button_query,
+                &mut text_query,
+            );
+            update_invert_labels(
+                &save_data.settings.key_bindings,
+                &localization,
+                &invert_query,
+                &mut text_query,
+            );
+        }
+    }
+}
+
+/// Handles interactions with the back button.
+fn controls_back_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ControlsBackButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Settings).unwrap();
+        }
+    }
+}
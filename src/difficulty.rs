@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// How hard the game is
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    /// The key for the name of this difficulty in the string table
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "common.difficulty_easy",
+            Difficulty::Normal => "common.difficulty_normal",
+            Difficulty::Hard => "common.difficulty_hard",
+        }
+    }
+
+    /// How long the press key has to be held down to snooze, when holding to press is enabled
+    pub fn press_hold_seconds(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.2,
+            Difficulty::Normal => 0.35,
+            Difficulty::Hard => 0.5,
+        }
+    }
+
+    /// How many minutes of sleep the emergency arm reset costs, or `None` if it isn't allowed
+    pub fn reset_penalty_minutes(&self) -> Option<u16> {
+        match self {
+            Difficulty::Easy => Some(5),
+            Difficulty::Normal => Some(10),
+            Difficulty::Hard => None,
+        }
+    }
+
+    /// Which way and how fast the phone slides across the table, in pixels per second
+    pub fn gravity(&self) -> Vec2 {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => Vec2::ZERO,
+            // a gentle slope towards the bottom edge, so it's always drifting away
+            Difficulty::Hard => Vec2::new(0.0, -15.0),
+        }
+    }
+
+    /// How many times the snooze button can be missed before the run ends, or `None` for no limit
+    pub fn miss_limit(&self) -> Option<u32> {
+        match self {
+            Difficulty::Easy => None,
+            Difficulty::Normal => Some(5),
+            Difficulty::Hard => Some(3),
+        }
+    }
+
+    /// How much flailing the arm and hand around ends the run, in radians spun recently, or `None` for no limit.
+    /// This is way more than careful play ever gets to.
+    pub fn shake_limit(&self) -> Option<f32> {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => None,
+            Difficulty::Hard => Some(15.0),
+        }
+    }
+
+    /// How long the player can leave the alarm going without touching anything before the run ends, in seconds, or `None` for no limit
+    pub fn idle_loss_seconds(&self) -> Option<f32> {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => None,
+            Difficulty::Hard => Some(90.0),
+        }
+    }
+
+    /// How long the phone waits between vibrations at the start of a run, in seconds
+    pub fn vibration_delay_seconds(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.8,
+            Difficulty::Normal => 1.5,
+            Difficulty::Hard => 1.3,
+        }
+    }
+
+    /// What the delay between vibrations gets multiplied by with each snooze
+    pub fn vibration_speedup(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.95,
+            Difficulty::Normal => 0.9,
+            Difficulty::Hard => 0.85,
+        }
+    }
+
+    /// The furthest a single vibration can move the phone, before it gets scaled by how intense things are
+    pub fn max_vibrate_translation(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 70.0,
+            Difficulty::Normal => 100.0,
+            Difficulty::Hard => 130.0,
+        }
+    }
+
+    /// How big the snooze button is, relative to its normal size
+    pub fn snooze_button_scale(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.3,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.85,
+        }
+    }
+
+    /// How many snooze buttons the phone has, only one of which works at a time
+    pub fn snooze_button_count(&self) -> usize {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => 1,
+            Difficulty::Hard => 2,
+        }
+    }
+
+    /// Whether there's other stuff on the table getting in the way
+    pub fn has_table_clutter(&self) -> bool {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => false,
+            Difficulty::Hard => true,
+        }
+    }
+
+    /// The time the player has to be up by to make it to work, or `None` for no deadline
+    pub fn deadline(&self) -> Option<GameTime> {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => None,
+            Difficulty::Hard => Some(GameTime {
+                hour: 10,
+                minute: 30,
+            }),
+        }
+    }
+
+    /// The finger that has to be the one on the snooze button for it to count, or `None` for any finger
+    pub fn required_finger(&self) -> Option<Finger> {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => None,
+            Difficulty::Hard => Some(Finger::Index),
+        }
+    }
+
+    /// How far and how forgivingly the hand can reach
+    pub fn reach(&self) -> Reach {
+        match self {
+            Difficulty::Easy => Reach {
+                extension: 1.2,
+                touch_area_scale: 1.3,
+            },
+            Difficulty::Normal => Reach::default(),
+            Difficulty::Hard => Reach {
+                extension: 0.9,
+                touch_area_scale: 0.85,
+            },
+        }
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Difficulty::Normal
+    }
+}
@@ -0,0 +1,119 @@
+use crate::*;
+
+/// How close to the edge of the table the phone has to get to count as a near fall
+const NEAR_FALL_DISTANCE: f32 = 60.0;
+/// Snoozing faster than this without any trouble counts as comfortable
+const COMFORTABLE_SNOOZE_SECONDS: f32 = 4.0;
+/// How much each snooze cycle counts towards the rolling performance rating
+const RATING_WEIGHT: f32 = 0.3;
+/// The most the vibration interval will be stretched or shrunk by, as a fraction of what it would otherwise be
+const MAX_INTERVAL_ADJUSTMENT: f32 = 0.15;
+
+pub struct DynamicDifficultyPlugin;
+
+impl Plugin for DynamicDifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Performance::default())
+            .add_system_set(
+                SystemSet::on_enter(GameState::Game).with_system(reset_performance_system),
+            )
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_run_criteria(game_running)
+                    .with_system(performance_tracking_system),
+            )
+            .add_system(difficulty_adjustment_system.after("snooze"));
+    }
+}
+
+/// How the player has been doing, for deciding whether to ease off or tighten up
+struct Performance {
+    /// From -1 (struggling) to 1 (cruising)
+    rating: f32,
+    /// The factor the vibration interval is currently being adjusted by
+    interval_factor: f32,
+    cycle_seconds: f32,
+    cycle_misses: u32,
+    cycle_near_fall: bool,
+    last_miss_count: u32,
+}
+
+impl Default for Performance {
+    fn default() -> Self {
+        Performance {
+            rating: 0.0,
+            interval_factor: 1.0,
+            cycle_seconds: 0.0,
+            cycle_misses: 0,
+            cycle_near_fall: false,
+            last_miss_count: 0,
+        }
+    }
+}
+
+/// Starts each run off with no adjustments
+fn reset_performance_system(mut performance: ResMut<Performance>) {
+    *performance = Performance::default();
+}
+
+/// Handles keeping track of how the current snooze cycle is going
+fn performance_tracking_system(
+    time: Res<Time>,
+    input_allowed: Res<InputAllowed>,
+    miss_count: Res<MissCount>,
+    bounds: Res<Bounds>,
+    mut performance: ResMut<Performance>,
+    phone_query: Query<&Transform, With<Phone>>,
+) {
+    if input_allowed.0 {
+        performance.cycle_seconds += time.delta_seconds();
+    }
+
+    if miss_count.0 > performance.last_miss_count {
+        performance.cycle_misses += miss_count.0 - performance.last_miss_count;
+    }
+    performance.last_miss_count = miss_count.0;
+
+    for transform in phone_query.iter() {
+        if bounds.table.distance_to_edge(transform.translation) < NEAR_FALL_DISTANCE {
+            performance.cycle_near_fall = true;
+        }
+    }
+}
+
+/// Handles nudging the vibration interval after each snooze, based on how the player is doing
+fn difficulty_adjustment_system(
+    save_data: Res<SaveData>,
+    mut performance: ResMut<Performance>,
+    mut vibrate_timer: ResMut<VibrateTimer>,
+    mut snooze_events: EventReader<SnoozeEvent>,
+) {
+    if snooze_events.iter().count() == 0 {
+        return;
+    }
+
+    let cycle_score = if performance.cycle_misses >= 2 || performance.cycle_near_fall {
+        -1.0
+    } else if performance.cycle_misses == 0
+        && performance.cycle_seconds < COMFORTABLE_SNOOZE_SECONDS
+    {
+        1.0
+    } else {
+        0.0
+    };
+    performance.rating =
+        (performance.rating * (1.0 - RATING_WEIGHT) + cycle_score * RATING_WEIGHT).clamp(-1.0, 1.0);
+    performance.cycle_seconds = 0.0;
+    performance.cycle_misses = 0;
+    performance.cycle_near_fall = false;
+
+    if !save_data.settings.dynamic_difficulty {
+        return;
+    }
+
+    // doing well means vibrating more often
+    let new_factor = 1.0 - performance.rating * MAX_INTERVAL_ADJUSTMENT;
+    let unadjusted_seconds = vibrate_timer.0.duration().as_secs_f32() / performance.interval_factor;
+    vibrate_timer.0 = Timer::from_seconds(unadjusted_seconds * new_factor, true);
+    performance.interval_factor = new_factor;
+}
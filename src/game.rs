@@ -1,40 +1,103 @@
 use std::time::Duration;
 
+use bevy::{app::AppExit, window::WindowCloseRequested};
 use bevy_asset_loader::{AssetCollection, AssetLoader};
 use bevy_kira_audio::{Audio, AudioChannel, AudioSource};
 use bevy_rapier2d::{physics::JointHandleComponent, prelude::*};
 use bevy_tweening::{
     component_animator_system,
-    lens::{TransformPositionLens, TransformRotationLens},
+    lens::{SpriteColorLens, TransformPositionLens, TransformRotationLens, TransformScaleLens},
     Animator, EaseFunction, Lens, Tracks, Tween, TweenCompleted, TweeningType,
 };
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
 
 use crate::*;
 
-const FADE_IN_TIME: Duration = Duration::from_secs(5);
-const FADE_OUT_TIME: Duration = Duration::from_secs(5);
+pub const FADE_IN_TIME: Duration = Duration::from_secs(5);
+pub const FADE_OUT_TIME: Duration = Duration::from_secs(5);
 const VIBRATE_TIME: Duration = Duration::from_millis(500);
-const VIBRATION_DELAY_SECONDS: f32 = 1.5;
-const MISS_PENALTY_SECONDS: f32 = 1.0;
+pub const MISS_PENALTY_SECONDS: f32 = 1.0;
+/// How long the player gets to sleep after the first snooze, before the alarm goes off again
+const SLEEP_SECONDS: f32 = 2.0;
+/// How much shorter the sleep gets after each snooze
+const SLEEP_SPEEDUP: f32 = 0.9;
+const MIN_SLEEP_SECONDS: f32 = 0.5;
+const DEATH_SEQUENCE_TIME: Duration = Duration::from_secs(3);
 
 const FADE_OUT_TWEEN_COMPLETED: u64 = 1;
 const FADE_IN_TWEEN_COMPLETED: u64 = 2;
 const VIBRATE_TWEEN_COMPLETED: u64 = 3;
+const TRAIL_TWEEN_COMPLETED: u64 = 4;
 
 const ALARM_SOUND: &str = "sounds/alarm.ogg";
+const LOUDER_ALARM_SOUND: &str = "sounds/alarm_louder.wav";
+const FRANTIC_ALARM_SOUND: &str = "sounds/alarm_frantic.wav";
+/// How many snoozes it takes for the alarm to get louder
+const LOUDER_ALARM_SNOOZES: u32 = 3;
+/// How many snoozes it takes for the alarm to get frantic
+const FRANTIC_ALARM_SNOOZES: u32 = 6;
 const HIT_SOUND: &str = "sounds/hit.ogg";
 const DROP_SOUND: &str = "sounds/drop_2.ogg";
 
 const ALARM_CHANNEL: &str = "alarm";
+const SFX_CHANNEL: &str = "sfx";
+/// Sound effects that get pitched up with the snooze streak, kept apart so the pitch doesn't affect anything else
+const COMBO_SFX_CHANNEL: &str = "combo_sfx";
+
+/// How much higher each snooze in a streak sounds than the last, as a playback rate
+const COMBO_PITCH_STEP: f32 = 0.06;
+const MAX_COMBO_PITCH: f32 = 1.6;
 
-const MAX_VIBRATE_TRANSLATION: f32 = 100.0;
 const MAX_VIBRATE_ROTATION: f32 = 0.75;
+/// How much of each vibration goes towards the nearest edge with the drift pattern, as a fraction of the max translation
+const DRIFT_BIAS: f32 = 0.4;
+/// How much of the way back to the middle of the table each vibration goes with the pulse pattern
+const PULSE_RETURN_FRACTION: f32 = 0.5;
+/// How much more violent each snooze makes the vibrations, as a multiplier on how far the phone moves and turns
+const SNOOZE_INTENSITY_STEP: f32 = 0.05;
+/// The most violent snoozing can make the vibrations, so they stay possible to keep up with
+const MAX_SNOOZE_INTENSITY: f32 = 1.5;
+/// How far inside the table edges each vibration has to end, so the phone can get close to falling but not fall on its own
+const VIBRATE_EDGE_INSET: f32 = 30.0;
+
+const TRAIL_MIN_SPEED: f32 = 250.0;
+const TRAIL_SPAWN_INTERVAL_SECONDS: f32 = 0.04;
+const TRAIL_FADE_TIME: Duration = Duration::from_millis(250);
+const TRAIL_STARTING_ALPHA: f32 = 0.35;
+const MAX_TRAIL_SPRITES: usize = 8;
+
+const TUMBLE_DISTANCE: f32 = 1200.0;
+const TUMBLE_ROTATION: f32 = 3.0;
+const TUMBLE_SCALE_FACTOR: f32 = 0.6;
+const DEATH_ZOOM_SCALE: f32 = 0.6;
+/// How long the phone takes to tumble out of view, before it's slowed down
+const TUMBLE_TIME: Duration = Duration::from_millis(750);
+/// How many times slower everything goes while the phone is falling off the table
+const SLOW_MOTION_FACTOR: u32 = 4;
 
 const OVERLAY_COLOR: Color = Color::BLACK;
+pub const GAME_OVER_PANEL_ALPHA: f32 = 0.7;
+/// The tint behind the game over text when the player won, so it doesn't look like another loss
+const WIN_PANEL_COLOR: Color = Color::rgba(0.1, 0.35, 0.15, GAME_OVER_PANEL_ALPHA);
+/// The tint behind the game over text when the player ran out of time, so it reads differently from knocking the phone off
+const LATE_PANEL_COLOR: Color = Color::rgba(0.45, 0.25, 0.05, GAME_OVER_PANEL_ALPHA);
 const HAND_CONTROL_POWER: f32 = 2.0;
 const ARM_CONTROL_POWER: f32 = 1.0;
 const ARM_EXTENSION_CONTROL_POWER: f32 = 150.0;
+/// How far off from lining up with the cursor the hand can be before the arm rotates toward it, in radians
+const MOUSE_STEER_ANGLE_DEADZONE: f32 = 0.03;
+/// How far off horizontally from the cursor the hand can be before the arm extends or retracts toward it
+const MOUSE_STEER_DISTANCE_DEADZONE: f32 = 20.0;
+/// How long the left mouse button can be held and still count as a click that presses
+const MOUSE_CLICK_SECONDS: f32 = 0.25;
+/// How long the extend key has to be held to fully charge a throw
+const MAX_CHARGE_SECONDS: f32 = 1.0;
+const MAX_THROW_SPEED: f32 = 900.0;
+/// How quickly a thrown arm slows down, in speed per second
+const THROW_DECELERATION: f32 = 1500.0;
+const CHARGE_METER_WIDTH: f32 = 150.0;
+const CHARGE_METER_HEIGHT: f32 = 12.0;
 const LINEAR_DAMPING: f32 = 1.0;
 const ANGULAR_DAMPING: f32 = 1.0;
 const HAND_MOTOR_FACTOR: f32 = 0.1;
@@ -42,29 +105,161 @@ const ARM_MOTOR_FACTOR: f32 = 0.05;
 
 const ARM_EXTENSION_LIMIT: f32 = 750.0;
 const ARM_RETRACTION_LIMIT: f32 = 1700.0;
+const MIN_REACH: f32 = 0.5;
+const MAX_REACH: f32 = 1.5;
 
-const ARM_ANCHOR_STARTING_POSITION_X: f32 = 1400.0;
-const ARM_ANCHOR_STARTING_POSITION_Y: f32 = 0.0;
 const ARM_ANCHOR_STARTING_POSITION_Z: f32 = 0.0;
 
-const TABLE_EDGE_LEFT: f32 = -577.0;
-const TABLE_EDGE_RIGHT: f32 = 440.0;
-const TABLE_EDGE_TOP: f32 = 370.0;
-const TABLE_EDGE_BOTTOM: f32 = -290.0;
+/// The poses a run can start in. All of them leave the snooze button reachable. The first one is used when starting poses aren't randomized.
+const STARTING_POSES: [StartingPose; 4] = [
+    StartingPose {
+        arm_anchor_x: 1400.0,
+        arm_anchor_y: 0.0,
+        arm_angle: -0.79,
+        hand_angle: 0.0,
+    },
+    StartingPose {
+        arm_anchor_x: 1300.0,
+        arm_anchor_y: 60.0,
+        arm_angle: -0.6,
+        hand_angle: 0.2,
+    },
+    StartingPose {
+        arm_anchor_x: 1500.0,
+        arm_anchor_y: -60.0,
+        arm_angle: -0.95,
+        hand_angle: -0.2,
+    },
+    StartingPose {
+        arm_anchor_x: 1350.0,
+        arm_anchor_y: -30.0,
+        arm_angle: -0.7,
+        hand_angle: 0.35,
+    },
+];
+
+pub const TABLE_EDGE_LEFT: f32 = -577.0;
+pub const TABLE_EDGE_RIGHT: f32 = 440.0;
+pub const TABLE_EDGE_TOP: f32 = 370.0;
+pub const TABLE_EDGE_BOTTOM: f32 = -290.0;
+
+const FINGER_HINT_MARKER_SIZE: f32 = 12.0;
+const FINGER_HINT_Z: f32 = 50.0;
+const FINGER_HINT_TEXT_OFFSET_Y: f32 = -60.0;
+const FINGER_HINT_VALID_COLOR: Color = Color::rgb(0.2, 0.9, 0.2);
+const FINGER_HINT_INVALID_COLOR: Color = Color::rgb(0.9, 0.2, 0.2);
+
+const ARM_HINT_SECONDS: f32 = 8.0;
+const ARM_HINT_MAX_USES: u32 = 3;
+const ARM_HINT_FADE_SPEED: f32 = 3.0;
+
+const DAYLIGHT_TINT_Z: f32 = 30.0;
+const DAYLIGHT_TINT_SIZE: f32 = 4000.0;
+const DAYLIGHT_TINT_CHANGE_SPEED: f32 = 0.5;
+/// The tint at and before dawn
+const DAWN_TINT: Color = Color::rgba(0.2, 0.3, 0.8, 0.15);
+const DAWN_MINUTES: i32 = 6 * 60;
+/// The tint at and after midday
+const MIDDAY_TINT: Color = Color::rgba(1.0, 0.8, 0.4, 0.1);
+const MIDDAY_MINUTES: i32 = 12 * 60;
+
+/// How far below the time display the snooze counter goes
+const SNOOZE_COUNTER_OFFSET_Y: f32 = 80.0;
+/// How big the snooze counter text is, compared to the time display
+const SNOOZE_COUNTER_FONT_SCALE: f32 = 0.35;
 
-const ROTATE_HAND_UP_KEY: KeyCode = KeyCode::W;
-const ROTATE_HAND_DOWN_KEY: KeyCode = KeyCode::S;
-const ROTATE_ARM_UP_KEY: KeyCode = KeyCode::Up;
-const ROTATE_ARM_DOWN_KEY: KeyCode = KeyCode::Down;
-const EXTEND_ARM_KEY: KeyCode = KeyCode::Left;
-const RETRACT_ARM_KEY: KeyCode = KeyCode::Right;
-const PRESS_KEY: KeyCode = KeyCode::Space;
+/// How fast the hand has to hit something, relative to it, to shake the screen at all
+const COLLISION_SHAKE_MIN_SPEED: f32 = 150.0;
+/// How fast the hand has to hit something, relative to it, to shake the screen as hard as it goes
+const COLLISION_SHAKE_MAX_SPEED: f32 = 1000.0;
+/// How far the camera can get shaken away from the middle
+const COLLISION_SHAKE_MAX_OFFSET: f32 = 20.0;
+/// How fast the shake settles down, in offset per second
+const COLLISION_SHAKE_DECAY: f32 = 80.0;
+
+const SCREEN_GLOW_WIDTH: f32 = 420.0;
+const SCREEN_GLOW_HEIGHT: f32 = 160.0;
+const SCREEN_GLOW_Z_OFFSET: f32 = 0.5;
+const SCREEN_GLOW_PULSE_SECONDS: f32 = 0.5;
+const SCREEN_GLOW_MAX_ALPHA: f32 = 0.35;
+const SCREEN_GLOW_COLOR: Color = Color::rgb(0.8, 0.9, 1.0);
+
+const NOTIFICATION_WIDTH: f32 = 320.0;
+const NOTIFICATION_HEIGHT: f32 = 80.0;
+const NOTIFICATION_Z: f32 = 2.0;
+const NOTIFICATION_FONT_SIZE: f32 = 36.0;
+const NOTIFICATION_COLOR: Color = Color::rgb(0.2, 0.2, 0.25);
+/// How far up and down the phone notifications can show up, relative to the phone
+const NOTIFICATION_MIN_Y: f32 = -250.0;
+const NOTIFICATION_MAX_Y: f32 = 150.0;
+const NOTIFICATION_MAX_X_OFFSET: f32 = 40.0;
+const MIN_NOTIFICATION_DELAY_SECONDS: f32 = 3.0;
+const MAX_NOTIFICATION_DELAY_SECONDS: f32 = 6.0;
+const NOTIFICATION_LIFETIME_SECONDS: f32 = 2.5;
+const NOTIFICATION_POP_TIME: Duration = Duration::from_millis(200);
+
+/// How many minutes before the wake goal things start getting tense
+const TENSION_START_MINUTES: f32 = 30.0;
+/// How quickly the tension catches up with how close the wake goal is
+const TENSION_CHANGE_SPEED: f32 = 0.5;
+const TENSION_TINT_Z: f32 = 31.0;
+const TENSION_TINT_COLOR: Color = Color::rgb(0.9, 0.1, 0.05);
+const TENSION_TINT_MAX_ALPHA: f32 = 0.15;
+/// How much more the phone vibrates at full tension, as a fraction of what it otherwise would
+const TENSION_EXTRA_VIBRATION: f32 = 0.5;
+
+/// How long after the idle timeout it takes for the alarm to get as insistent as it's going to get
+const IDLE_ESCALATION_RAMP_SECONDS: f32 = 10.0;
+/// How much more the phone vibrates once the player has ignored it for long enough, as a fraction of what it otherwise would
+const IDLE_EXTRA_VIBRATION: f32 = 1.0;
+
+/// How quickly old movement stops counting towards the shake meter, as a fraction per second
+const SHAKE_DECAY: f32 = 1.0;
+
+/// How long input stays ignored after the arm's joints show up in the physics world, so they can settle in first
+const JOINT_SETTLING_SECONDS: f32 = 0.1;
+
+const AUTO_RETRY_DELAY_SECONDS: f32 = 1.5;
+const AUTO_RETRY_CANCEL_KEY: KeyCode = KeyCode::Escape;
 
 const SNOOZE_MINUTES: u16 = 7;
+
+/// How long the alarm goes on before the game snoozes it for a player who needs help
+const SNOOZE_ASSIST_SECONDS: f32 = 20.0;
+/// How long the message about the game snoozing for the player stays up
+const SNOOZE_ASSIST_MESSAGE_SECONDS: f32 = 5.0;
+
+const MISS_INDICATOR_COLOR: Color = Color::rgb(0.9, 0.2, 0.2);
+const SNOOZE_BUTTON_GAP: f32 = 20.0;
+const INACTIVE_SNOOZE_BUTTON_DIMMING: f32 = 0.3;
+/// How much the snooze button brightens towards white while a finger is on it
+const SNOOZE_BUTTON_HIGHLIGHT: f32 = 0.5;
+/// How long the snooze button takes to fully brighten once a finger is on it
+const SNOOZE_BUTTON_HIGHLIGHT_SECONDS: f32 = 0.1;
 const MINUTES_PER_HOUR: u16 = 60;
 const HOURS_PER_DAY: u16 = 24;
 
 const STARTING_TIME: GameTime = GameTime { hour: 8, minute: 0 };
+pub const WAKE_GOAL: GameTime = GameTime { hour: 9, minute: 0 };
+
+const DEV_GRAVITY_STEP: f32 = 5.0;
+
+const CALIBRATION_OVERLAY_KEY: KeyCode = KeyCode::F4;
+const CALIBRATION_GRID_SPACING: f32 = 50.0;
+/// How far the grid goes out from the middle of the scene in each direction
+const CALIBRATION_GRID_EXTENT: f32 = 1000.0;
+const CALIBRATION_GRID_Z: f32 = 60.0;
+const CALIBRATION_GRID_COLOR: Color = Color::rgba(0.0, 1.0, 1.0, 0.15);
+const CALIBRATION_AXIS_COLOR: Color = Color::rgba(0.0, 1.0, 1.0, 0.6);
+
+/// Arm parts further than this from the middle of the scene have flown off somewhere they shouldn't be
+const MAX_SANE_POSITION: f32 = 10_000.0;
+/// Arm parts moving faster than this have been flung by the physics blowing up
+const MAX_SANE_LINEAR_VELOCITY: f32 = 20_000.0;
+const MAX_SANE_ANGULAR_VELOCITY: f32 = 500.0;
+
+const NIGHT_OWL_INTENSITY_INCREASE: f32 = 0.1;
+const NIGHT_OWL_MAX_INTENSITY: f32 = 2.5;
 
 pub struct GamePlugin;
 
@@ -74,48 +269,252 @@ impl Plugin for GamePlugin {
             .continue_to_state(GameState::Game)
             .with_collection::<ImageAssets>()
             .with_collection::<AudioAssets>()
+            .with_collection::<SceneAssets>()
             .build(app);
 
         app.add_system_set(
             SystemSet::on_enter(GameState::Game)
-                .with_system(game_setup)
-                .with_system(alarm_sound_system),
+                .with_system(reset_game_state.label("reset_game_state"))
+                .with_system(tuning_setup.label("tuning_setup").after("reset_game_state"))
+                .with_system(game_setup.label("game_setup").after("tuning_setup"))
+                .with_system(seed_display_setup.after("game_setup"))
+                .with_system(finger_hint_setup)
+                .with_system(arm_hint_setup)
+                .with_system(charge_meter_setup)
+                .with_system(obstacle_setup)
+                .with_system(physics_quality_setup)
+                .with_system(daylight_tint_setup)
+                .with_system(tension_tint_setup)
+                .with_system(misses_display_setup.after("tuning_setup"))
+                .with_system(deadline_setup.label("deadline_setup"))
+                .with_system(deadline_display_setup.after("deadline_setup"))
+                .with_system(streak_display_setup.after("tuning_setup"))
+                .with_system(snooze_preview_setup.after("tuning_setup"))
+                .with_system(snooze_assist_setup)
+                .with_system(idle_setup)
+                .with_system(alarm_sound_system.after("reset_game_state")),
         )
         .add_system_set(
             SystemSet::on_exit(GameState::Game)
-                .with_system(despawn_components_system::<GameComponent>),
+                .with_system(despawn_components_system::<GameComponent>)
+                .with_system(end_death_sequence)
+                .with_system(stop_alarm)
+                .with_system(leave_run_system)
+                .with_system(clear_game_events),
         )
         .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
-        .add_plugin(RapierRenderPlugin) //TODO
         .insert_resource(RapierConfiguration {
             gravity: Vector::zeros(),
             ..Default::default()
         })
         .add_event::<FadeEvent>()
         .add_event::<SnoozeEvent>()
+        .add_event::<MissEvent>()
+        .add_event::<GameLogEvent>()
+        .add_event::<GameOverEvent>()
+        .add_event::<GameResultEvent>()
+        .insert_resource(LastGameResult(None))
+        .insert_resource(AutoRetryTimer(None))
         .add_event::<TweenCompleted>()
         .insert_resource(STARTING_TIME)
-        .insert_resource(ValidPressPosition(false))
+        .insert_resource(ValidPressPosition(None))
+        .insert_resource(ValidDismissPosition(false))
+        .insert_resource(FingerContact::default())
         .insert_resource(InputAllowed(true))
         .insert_resource(AlarmActive(true))
+        .insert_resource(AlarmChannel(AudioChannel::new(ALARM_CHANNEL.to_string())))
         .insert_resource(VibrateTimer(Timer::from_seconds(
-            VIBRATION_DELAY_SECONDS,
+            Difficulty::default().vibration_delay_seconds(),
             true,
         )))
         .insert_resource(MissTimer(Timer::from_seconds(MISS_PENALTY_SECONDS, false)))
         .insert_resource(NumSnoozes(0))
+        .insert_resource(MissCount(0))
+        .insert_resource(SnoozeStreak(0))
+        .insert_resource(SnoozeButtonLayout {
+            count: 1,
+            active: 0,
+            scale: 1.0,
+        })
+        .insert_resource(SnoozeReward::default())
+        .insert_resource(MissLimit(None))
+        .insert_resource(Deadline(None))
+        .insert_resource(GameOverReason::PhoneFell)
+        .insert_resource(DeathSequence(None))
+        .insert_resource(PressHoldTimer(None))
+        .insert_resource(SleepTimer(None))
+        .insert_resource(ArmCharge::default())
+        .insert_resource(JointSettling::default())
+        .insert_resource(SnoozeAssist {
+            available: false,
+            assisting: false,
+            timer: Timer::from_seconds(SNOOZE_ASSIST_SECONDS, false),
+        })
+        .insert_resource(GameRng::new(0))
+        .insert_resource(STARTING_POSES[0])
+        .insert_resource(RestPositions {
+            arm: Vec3::ZERO,
+            hand: Vec3::ZERO,
+        })
+        .insert_resource(Reach::default())
+        .insert_resource(WakeGoal(WAKE_GOAL))
+        .insert_resource(Bounds::default())
+        .insert_resource(CameraShake(0.0))
+        .insert_resource(MissPenalty(Some(MISS_PENALTY_SECONDS)))
+        .insert_resource(VibrationIntensity(1.0))
+        .insert_resource(Tension(0.0))
+        .insert_resource(Idle::default())
+        .insert_resource(ShakeMeter {
+            amount: 0.0,
+            limit: None,
+        })
+        .insert_resource(VibrationPattern::default())
+        .insert_resource(TweenEasing::default())
+        .insert_resource(Notifications {
+            enabled: false,
+            timer: Timer::from_seconds(MAX_NOTIFICATION_DELAY_SECONDS, false),
+        })
+        .insert_resource(Gravity(Vec2::ZERO))
+        .insert_resource(Theme::default())
+        .insert_resource(ArmHintTimer(Timer::from_seconds(ARM_HINT_SECONDS, false)))
+        .insert_resource(ArmHintUses(0))
+        .insert_resource(PhoneTrail {
+            last_position: None,
+            spawn_timer: Timer::from_seconds(TRAIL_SPAWN_INTERVAL_SECONDS, true),
+        })
         .add_system(component_animator_system::<UiColor>)
         .add_system(fade_system.label("fade").after("sleep"))
-        .add_system(hand_rotation_system)
-        .add_system(arm_rotation_system)
-        .add_system(arm_extension_system)
         .add_system(valid_press_position_system.label("valid_press"))
-        .add_system(press_system.label("press").after("valid_press"))
-        .add_system(snooze_system.label("snooze").after("press"))
+        .add_system(finger_hint_system.after("valid_press"))
         .add_system(sleep_system.label("sleep").after("snooze"))
-        .add_system(vibration_system)
-        .add_system(table_bounds_system)
-        .add_system(miss_penalty_system);
+        .add_system(exit_audio_system)
+        .add_system(streak_display_system)
+        .add_system(snooze_preview_system.after("snooze"))
+        .add_system(snooze_counter_system.after("snooze"))
+        .add_system_to_stage(CoreStage::PreUpdate, mouse_action_system.after("actions"))
+        .add_system(time_display_scale_system)
+        .add_system(daylight_tint_system)
+        .add_system(tension_tint_system.after("tension"))
+        .add_system_set(
+            SystemSet::on_update(GameState::Game)
+                // these need the loaded audio, so they can't run until the game has been loaded
+                .with_system(snooze_system.label("snooze").after("press"))
+                .with_system(game_over_sound_system.after("death_sequence"))
+                .with_system(auto_retry_system)
+                .with_system(try_again_button_system)
+                .with_system(game_over_menu_button_system),
+        )
+        .add_system(snooze_button_rotation_system.label("snooze_button_rotation"))
+        .add_system(
+            snooze_button_highlight_system
+                .label("snooze_button_highlight")
+                .after("snooze_button_rotation"),
+        )
+        .add_system(
+            snooze_highlight_system
+                .after("snooze_button_highlight")
+                .after("valid_press"),
+        )
+        .add_system_set(
+            SystemSet::new()
+                .with_run_criteria(game_running)
+                .with_system(joint_settling_system.label("joint_settling"))
+                .with_system(hand_rotation_system.after("joint_settling"))
+                .with_system(arm_rotation_system.after("joint_settling"))
+                .with_system(arm_extension_system.label("arm_extension"))
+                .with_system(arm_anchor_bounds_system.after("arm_extension"))
+                .with_system(charge_meter_system.after("arm_extension"))
+                .with_system(valid_dismiss_position_system.label("valid_dismiss"))
+                .with_system(
+                    press_system
+                        .label("press")
+                        .after("valid_press")
+                        .after("valid_dismiss"),
+                )
+                .with_system(tension_system.label("tension"))
+                .with_system(vibration_system.after("tension").after("idle"))
+                .with_system(phone_gravity_system)
+                .with_system(notification_spawn_system)
+                .with_system(notification_dismiss_system)
+                .with_system(arm_hint_system)
+                .with_system(phone_trail_system)
+                .with_system(screen_glow_system)
+                .with_system(trail_cleanup_system)
+                .with_system(
+                    miss_penalty_system
+                        .label("miss_penalty")
+                        .after("press")
+                        .after("hazard"),
+                )
+                .with_system(miss_feedback_system.after("miss_penalty"))
+                .with_system(wake_system.after("sleep"))
+                .with_system(emergency_reset_system)
+                .with_system(physics_recovery_system)
+                .with_system(hazard_system.label("hazard"))
+                .with_system(collision_shake_system.label("collision_shake"))
+                .with_system(camera_shake_system.after("collision_shake")),
+        )
+        .add_system_set(
+            // anything that can end the run, kept to the game so a run that's been left can't end on its own
+            SystemSet::on_update(GameState::Game)
+                .with_run_criteria(game_running)
+                .with_system(
+                    dismiss_system
+                        .label("game_over_triggers")
+                        .after("valid_dismiss"),
+                )
+                .with_system(idle_system.label("idle").label("game_over_triggers"))
+                .with_system(win_check_system.label("game_over_triggers"))
+                .with_system(deadline_system.label("game_over_triggers"))
+                .with_system(table_bounds_system.label("game_over_triggers"))
+                .with_system(shake_system.label("game_over_triggers"))
+                .with_system(
+                    miss_limit_system
+                        .label("game_over_triggers")
+                        .after("miss_penalty"),
+                )
+                .with_system(slow_motion_system.after("game_over_triggers"))
+                .with_system(
+                    game_over_system
+                        .label("game_over")
+                        .after("game_over_triggers"),
+                )
+                .with_system(death_sequence_system.label("death_sequence"))
+                .with_system(game_over_screen_system.after("death_sequence")),
+        )
+        .add_system_set(
+            // the assist snoozes and puts up a message, neither of which should happen after leaving a run
+            SystemSet::on_update(GameState::Game)
+                .with_run_criteria(game_running)
+                .with_system(snooze_assist_tracking_system.label("snooze_assist_tracking"))
+                .with_system(snooze_assist_system.after("snooze_assist_tracking"))
+                .with_system(snooze_assist_message_system),
+        );
+
+        if DEV_MODE {
+            // draw the colliders
+            app.add_plugin(RapierRenderPlugin);
+            app.add_system(dev_gravity_system)
+                .add_system(log_events_system);
+            app.insert_resource(CalibrationOverlay(false))
+                .insert_resource(DriftSamples::default())
+                .add_system_set(
+                    SystemSet::on_enter(GameState::Game)
+                        .with_system(calibration_overlay_setup)
+                        .with_system(drift_samples_reset_system),
+                )
+                .add_system_set(
+                    SystemSet::on_update(GameState::Game)
+                        .with_system(drift_sample_system.label("drift_sample"))
+                        .with_system(drift_summary_system.after("drift_sample").after("snooze")),
+                )
+                .add_system(calibration_overlay_toggle_system.label("calibration_overlay_toggle"))
+                .add_system(
+                    calibration_readout_system
+                        .after("calibration_overlay_toggle")
+                        .after("drift_sample"),
+                );
+        }
     }
 
     fn name(&self) -> &str {
@@ -127,10 +526,20 @@ impl Plugin for GamePlugin {
 struct AudioAssets {
     #[asset(path = "sounds/alarm.ogg")]
     alarm: Handle<AudioSource>,
+    #[asset(path = "sounds/alarm_louder.wav")]
+    louder_alarm: Handle<AudioSource>,
+    #[asset(path = "sounds/alarm_frantic.wav")]
+    frantic_alarm: Handle<AudioSource>,
     #[asset(path = "sounds/hit.ogg")]
     hit: Handle<AudioSource>,
     #[asset(path = "sounds/drop_2.ogg")]
     drop: Handle<AudioSource>,
+    #[asset(path = "sounds/snooze_chime.wav")]
+    snooze: Handle<AudioSource>,
+    #[asset(path = "sounds/game_over_win.wav")]
+    game_over_win: Handle<AudioSource>,
+    #[asset(path = "sounds/game_over_lose.wav")]
+    game_over_lose: Handle<AudioSource>,
 }
 
 #[derive(AssetCollection)]
@@ -151,6 +560,12 @@ struct GameComponent;
 #[derive(Component)]
 struct GameLoadingComponent;
 
+#[derive(Component)]
+struct TryAgainButton;
+
+#[derive(Component)]
+struct GameOverMenuButton;
+
 #[derive(Component)]
 struct Overlay;
 
@@ -164,96 +579,849 @@ struct Arm;
 struct ArmAnchor;
 
 #[derive(Component)]
-struct Phone;
+pub struct Phone;
 
 #[derive(Component)]
 struct TimeDisplay;
 
+/// Shows how many times the player has snoozed so far, just below the time
+#[derive(Component)]
+struct SnoozeCounter;
+
+/// Something on the table that gets in the way
+#[derive(Component)]
+struct Obstacle;
+
+/// An obstacle that counts against the player if the hand bumps into it
+#[derive(Component)]
+struct Hazard;
+
+/// The glow of the phone's screen while the alarm is going off
+#[derive(Component, Default)]
+struct ScreenGlow {
+    /// How far through the current pulse it is, from 0 to 1
+    phase: f32,
+}
+
+/// A snooze button, and which one it is when there's more than one
+#[derive(Component)]
+struct SnoozeButton(usize);
+
+/// The button for turning the alarm off and getting up
 #[derive(Component)]
-struct SnoozeButton;
+struct DismissButton;
 
 #[derive(Component)]
 struct TouchArea;
 
-struct ValidPressPosition(bool);
+/// Which finger a touch area is on
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum Finger {
+    Thumb,
+    Index,
+    Middle,
+    Ring,
+    Pinky,
+}
+
+#[derive(Component)]
+struct ArmHint;
+
+#[derive(Component)]
+struct TrailSprite;
+
+#[derive(Component)]
+struct MissesDisplay;
+
+#[derive(Component)]
+struct SeedDisplay;
+
+#[derive(Component)]
+struct StreakDisplay;
+
+#[derive(Component)]
+struct SnoozePreview;
+
+#[derive(Component)]
+struct ChargeMeter;
+
+/// A fake notification on the phone that gets in the way of the snooze button
+#[derive(Component)]
+struct Notification {
+    /// How much longer it stays up, or how much longer it takes to go away once it's going
+    timer: Timer,
+    dismissing: bool,
+}
+
+/// Whether notifications pop up on the phone this run, and how long until the next one
+struct Notifications {
+    enabled: bool,
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct DaylightTint;
+
+#[derive(Component)]
+struct TensionTint;
+
+/// How much the arm and hand have been flailing around lately, and how much is too much on this difficulty
+struct ShakeMeter {
+    amount: f32,
+    limit: Option<f32>,
+}
+
+/// How close the player is to making it to the wake goal, from 0 (nowhere near) to 1 (right there)
+pub struct Tension(pub f32);
 
-struct InputAllowed(bool);
+#[derive(Component)]
+struct FingerHintMarker;
+
+#[derive(Component)]
+struct FingerHintText;
+
+/// Which finger is in the correct position to press snooze, if any
+struct ValidPressPosition(Option<Finger>);
+
+/// Whether a finger is on the dismiss button, if there is one
+struct ValidDismissPosition(bool);
+
+/// Where a finger is touching the snooze button, or how far the closest one is from it if none are
+#[derive(Default)]
+struct FingerContact {
+    contact_point: Option<Vec2>,
+    nearest_distance: Option<f32>,
+}
+
+pub struct InputAllowed(pub bool);
 
-struct AlarmActive(bool);
+pub struct AlarmActive(pub bool);
 
-struct VibrateTimer(Timer);
+/// The channel the alarm plays in, kept around so it's always the same one being started and stopped
+pub struct AlarmChannel(pub AudioChannel);
+
+pub struct VibrateTimer(pub Timer);
 
 struct MissTimer(Timer);
 
+/// Source of randomness for a run, so runs can be reproduced from their seed
+pub struct GameRng {
+    pub seed: u64,
+    pub rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> GameRng {
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+/// Where the arm and hand start out at the beginning of a run
+#[derive(Clone, Copy)]
+struct StartingPose {
+    arm_anchor_x: f32,
+    arm_anchor_y: f32,
+    arm_angle: f32,
+    hand_angle: f32,
+}
+
+/// Where the arm and hand were spawned at the beginning of a run
+struct RestPositions {
+    arm: Vec3,
+    hand: Vec3,
+}
+
+/// How far and how forgivingly the hand can reach
+#[derive(Clone, Copy)]
+pub struct Reach {
+    /// Multiplier on how far the arm can extend
+    pub extension: f32,
+    /// Multiplier on the size of the fingertips
+    pub touch_area_scale: f32,
+}
+
+impl Default for Reach {
+    fn default() -> Self {
+        Reach {
+            extension: 1.0,
+            touch_area_scale: 1.0,
+        }
+    }
+}
+
+impl Reach {
+    /// The lowest X position the arm anchor can be extended to, within the provided limits
+    fn extension_limit(&self, arm_limits: &ArmLimits) -> f32 {
+        let range = arm_limits.retraction - arm_limits.extension;
+        arm_limits.retraction - (range * self.extension.clamp(MIN_REACH, MAX_REACH))
+    }
+
+    /// The highest X position the arm anchor can be retracted to, within the provided limits
+    fn retraction_limit(&self, arm_limits: &ArmLimits) -> f32 {
+        arm_limits.retraction
+    }
+}
+
+/// How far things can go before they're out of bounds
+#[derive(Clone, Copy, Default)]
+pub struct Bounds {
+    /// The edges of the table the phone sits on
+    pub table: TableBounds,
+    /// How far the arm anchor can move
+    pub arm: ArmLimits,
+}
+
+/// How far the arm anchor can move, before the reach for the difficulty is taken into account
+#[derive(Clone, Copy)]
+pub struct ArmLimits {
+    /// The lowest X position the arm anchor can be extended to with normal reach
+    pub extension: f32,
+    /// The highest X position the arm anchor can be retracted to
+    pub retraction: f32,
+}
+
+impl Default for ArmLimits {
+    fn default() -> Self {
+        ArmLimits {
+            extension: ARM_EXTENSION_LIMIT,
+            retraction: ARM_RETRACTION_LIMIT,
+        }
+    }
+}
+
+/// How charged up the next arm throw is, and how fast the last one is still going, when charged throws are enabled
+#[derive(Default)]
+struct ArmCharge {
+    seconds: f32,
+    throw_speed: f32,
+}
+
+impl ArmCharge {
+    /// How charged up the next throw is, from 0 to 1
+    fn fraction(&self) -> f32 {
+        (self.seconds / MAX_CHARGE_SECONDS).min(1.0)
+    }
+}
+
+/// Tracks how long the press key has been held down in a valid position, when holding to press is enabled
+struct PressHoldTimer(Option<Timer>);
+
+/// How much longer the player gets to sleep before the alarm goes off again, if they're asleep
+struct SleepTimer(Option<Timer>);
+
+impl SleepTimer {
+    /// Starts the sleep after the provided number of snoozes, which gets shorter the more snoozes there have been
+    fn start(&mut self, num_snoozes: u32) {
+        let seconds = SLEEP_SECONDS * SLEEP_SPEEDUP.powi(num_snoozes.saturating_sub(1) as i32);
+        self.0 = Some(Timer::from_seconds(seconds.max(MIN_SLEEP_SECONDS), false));
+    }
+}
+
 struct NumSnoozes(u32);
 
-struct GameTime {
-    hour: u16,
-    minute: u16,
+/// How long the arm extension hints stay up at the start of a run
+struct ArmHintTimer(Timer);
+
+/// The number of times the player has extended or retracted the arm, for deciding when they don't need hints anymore
+struct ArmHintUses(u32);
+
+/// How many snooze buttons the phone has, which one actually works right now, and how big they are relative to normal
+pub struct SnoozeButtonLayout {
+    pub count: usize,
+    pub active: usize,
+    pub scale: f32,
 }
 
-impl GameTime {
-    /// Advances the time for a snooze
-    fn snooze(&mut self) {
-        let new_minute = (self.minute + SNOOZE_MINUTES) % MINUTES_PER_HOUR;
-        let new_hour = if new_minute < self.minute {
-            (self.hour + 1) % HOURS_PER_DAY
-        } else {
-            self.hour
+/// The number of snoozes in a row since the last miss
+pub struct SnoozeStreak(pub u32);
+
+/// How many minutes each snooze grants, depending on the current streak
+#[derive(Clone, Copy)]
+pub struct SnoozeReward {
+    pub base_minutes: u16,
+    /// Extra minutes granted for each snooze in the current streak
+    pub bonus_minutes_per_streak: u16,
+    pub max_bonus_minutes: u16,
+}
+
+impl Default for SnoozeReward {
+    fn default() -> Self {
+        SnoozeReward {
+            base_minutes: SNOOZE_MINUTES,
+            bonus_minutes_per_streak: 0,
+            max_bonus_minutes: 0,
+        }
+    }
+}
+
+impl SnoozeReward {
+    /// The number of minutes the next snooze will grant with the provided streak
+    pub fn minutes(&self, streak: u32) -> u16 {
+        let streak = u16::try_from(streak).unwrap_or(u16::MAX);
+        let bonus = self
+            .bonus_minutes_per_streak
+            .saturating_mul(streak)
+            .min(self.max_bonus_minutes);
+        self.base_minutes + bonus
+    }
+
+    /// Whether streaks make any difference
+    pub fn scales(&self) -> bool {
+        self.bonus_minutes_per_streak > 0 && self.max_bonus_minutes > 0
+    }
+}
+
+/// The number of times the snooze button has been missed this run
+pub struct MissCount(pub u32);
+
+/// The number of misses allowed before the run ends, if there's a limit
+pub struct MissLimit(pub Option<u32>);
+
+/// How far the camera is currently getting shaken around from the hand hitting something
+struct CameraShake(f32);
+
+/// The time the player has to be up by before the run ends, if there's a deadline
+pub struct Deadline(pub Option<GameTime>);
+
+/// Shows how long is left until the deadline
+#[derive(Component)]
+struct DeadlineDisplay;
+
+/// Why the run ended
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum GameOverReason {
+    PhoneFell,
+    TooManyMisses,
+    Panicked,
+    /// Dismissed the alarm and got out of bed on purpose
+    GotUp,
+    /// Left the alarm going without touching anything for too long
+    FellAsleep,
+    /// Snoozed past the wake goal in a mode that has to be up on time
+    Overslept,
+    /// Snoozed as many times as the mode allows and got up on time
+    FinallyUp,
+    /// Snoozed past the deadline for the difficulty
+    LateForWork,
+}
+
+impl GameOverReason {
+    /// Whether ending the run this way counts as a win
+    pub fn is_win(&self) -> bool {
+        match self {
+            GameOverReason::GotUp | GameOverReason::FinallyUp => true,
+            GameOverReason::PhoneFell
+            | GameOverReason::TooManyMisses
+            | GameOverReason::Panicked
+            | GameOverReason::FellAsleep
+            | GameOverReason::Overslept
+            | GameOverReason::LateForWork => false,
+        }
+    }
+}
+
+/// How the phone moves around each time it vibrates
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VibrationPattern {
+    /// To a random spot anywhere nearby
+    Jitter,
+    /// Like jitter, but leaning further and further towards whichever edge it's already closest to
+    Drift,
+    /// Like jitter, but pulled back towards the middle of the table each time
+    Pulse,
+}
+
+impl Default for VibrationPattern {
+    fn default() -> Self {
+        VibrationPattern::Jitter
+    }
+}
+
+impl VibrationPattern {
+    /// Picks where the phone should vibrate to from the provided position
+    fn target(&self, start_position: Vec3, max_translation: f32, rng: &mut StdRng) -> Vec3 {
+        let jitter = Vec2::new(
+            rng.gen_range(-max_translation..max_translation),
+            rng.gen_range(-max_translation..max_translation),
+        );
+        let start = start_position.truncate();
+        let end = match self {
+            VibrationPattern::Jitter => start + jitter,
+            VibrationPattern::Drift => {
+                let outwards = start.normalize_or_zero();
+                start + jitter * (1.0 - DRIFT_BIAS) + outwards * max_translation * DRIFT_BIAS
+            }
+            VibrationPattern::Pulse => start * (1.0 - PULSE_RETURN_FRACTION) + jitter,
         };
 
-        self.minute = new_minute;
-        self.hour = new_hour;
+        end.extend(start_position.z)
+    }
+}
+
+/// How the fade and vibration tweens speed up and slow down
+#[derive(Clone, Copy)]
+pub struct TweenEasing {
+    pub fade: EaseFunction,
+    pub vibration: EaseFunction,
+}
+
+impl Default for TweenEasing {
+    fn default() -> Self {
+        TweenEasing {
+            fade: EaseFunction::SineInOut,
+            vibration: EaseFunction::SineInOut,
+        }
+    }
+}
+
+/// Colors that set the mood of a run
+#[derive(Clone, Copy)]
+pub struct Theme {
+    /// What the screen fades to between snoozes. Should be fully opaque.
+    pub overlay_color: Color,
+    /// The tint behind the game over text
+    pub game_over_panel_color: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            overlay_color: OVERLAY_COLOR,
+            game_over_panel_color: with_alpha(OVERLAY_COLOR, GAME_OVER_PANEL_ALPHA),
+        }
+    }
+}
+
+/// Returns the provided color with its alpha replaced
+pub fn with_alpha(mut color: Color, alpha: f32) -> Color {
+    color.set_a(alpha);
+    color
+}
+
+/// Which way and how fast the phone slides across the table, in pixels per second
+pub struct Gravity(pub Vec2);
+
+/// The time the player is supposed to get up at
+pub struct WakeGoal(pub GameTime);
+
+/// Tracks the phone's movement for leaving a trail behind it
+struct PhoneTrail {
+    last_position: Option<Vec3>,
+    spawn_timer: Timer,
+}
+
+/// The edges of the table the phone sits on
+#[derive(Clone, Copy)]
+pub struct TableBounds {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl Default for TableBounds {
+    fn default() -> Self {
+        TableBounds {
+            left: TABLE_EDGE_LEFT,
+            right: TABLE_EDGE_RIGHT,
+            top: TABLE_EDGE_TOP,
+            bottom: TABLE_EDGE_BOTTOM,
+        }
+    }
+}
+
+impl TableBounds {
+    /// Whether the provided position is on the table
+    pub fn contains(&self, position: Vec3) -> bool {
+        position.x >= self.left
+            && position.x <= self.right
+            && position.y <= self.top
+            && position.y >= self.bottom
+    }
+
+    /// How far the provided position is from the nearest edge. Negative if it's outside the bounds.
+    pub fn distance_to_edge(&self, position: Vec3) -> f32 {
+        (position.x - self.left)
+            .min(self.right - position.x)
+            .min(self.top - position.y)
+            .min(position.y - self.bottom)
+    }
+
+    /// The closest position to the provided one that's at least the provided distance inside the edges, or no closer to
+    /// them than `from` is. Moving from `from` to the result never gets any closer to falling off past the inset.
+    pub fn clamp_inside(&self, position: Vec3, from: Vec3, inset: f32) -> Vec3 {
+        // don't let the inset cross over itself on a small table
+        let inset_x = inset.min((self.right - self.left) / 2.0).max(0.0);
+        let inset_y = inset.min((self.top - self.bottom) / 2.0).max(0.0);
+        Vec3::new(
+            position.x.clamp(
+                (self.left + inset_x).min(from.x),
+                (self.right - inset_x).max(from.x),
+            ),
+            position.y.clamp(
+                (self.bottom + inset_y).min(from.y),
+                (self.top - inset_y).max(from.y),
+            ),
+            position.z,
+        )
+    }
+}
+
+/// How many seconds input is locked for after missing the snooze button, if at all
+pub struct MissPenalty(pub Option<f32>);
+
+/// Multiplier on how far the phone moves each time it vibrates
+struct VibrationIntensity(f32);
+
+/// Times the dramatic tumble between the phone falling off the table and the game over screen
+pub struct DeathSequence(Option<Timer>);
+
+impl DeathSequence {
+    /// Whether the run is over and on its way to the game over screen
+    pub fn is_started(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct GameTime {
+    pub hour: u16,
+    pub minute: u16,
+}
+
+impl GameTime {
+    /// Advances the time by the provided number of minutes, wrapping around at midnight
+    pub fn advance(&mut self, minutes: u16) {
+        let minutes_per_day = u32::from(MINUTES_PER_HOUR) * u32::from(HOURS_PER_DAY);
+        let total_minutes = (u32::from(self.hour) * u32::from(MINUTES_PER_HOUR)
+            + u32::from(self.minute)
+            + u32::from(minutes))
+            % minutes_per_day;
+
+        self.hour = (total_minutes / u32::from(MINUTES_PER_HOUR)) as u16;
+        self.minute = (total_minutes % u32::from(MINUTES_PER_HOUR)) as u16;
+    }
+
+    /// The number of minutes since midnight
+    pub fn minutes_since_midnight(&self) -> i32 {
+        i32::from(self.hour) * i32::from(MINUTES_PER_HOUR) + i32::from(self.minute)
+    }
+
+    /// The number of minutes from this time until the next time it's the provided one, wrapping around at midnight
+    pub fn minutes_until(&self, other: &GameTime) -> i32 {
+        let minutes_per_day = i32::from(MINUTES_PER_HOUR) * i32::from(HOURS_PER_DAY);
+        (other.minutes_since_midnight() - self.minutes_since_midnight()).rem_euclid(minutes_per_day)
+    }
 
-        println!("Advanced time to {self}"); //TODO
+    /// The number of minutes this time is after the provided one, counting from the start of a run so the clock going
+    /// past midnight doesn't throw it off. Negative if the provided time is later.
+    pub fn minutes_past(&self, other: &GameTime) -> i32 {
+        STARTING_TIME.minutes_until(self) - STARTING_TIME.minutes_until(other)
     }
 }
 
 impl std::fmt::Display for GameTime {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (converted_hour, am_or_pm) = if self.hour > 12 {
-            (self.hour - 12, "PM")
-        } else {
-            (self.hour, "AM")
+        let (converted_hour, am_or_pm) = match self.hour {
+            0 => (12, "AM"),
+            1..=11 => (self.hour, "AM"),
+            12 => (12, "PM"),
+            _ => (self.hour - 12, "PM"),
         };
 
         write!(f, "{converted_hour}:{:02} {am_or_pm}", self.minute)
     }
 }
 
-struct FadeEvent(FadeDirection);
+#[cfg(test)]
+mod game_time_tests {
+    use super::*;
+
+    fn display(hour: u16, minute: u16) -> String {
+        GameTime { hour, minute }.to_string()
+    }
+
+    #[test]
+    fn midnight_is_12_am() {
+        assert_eq!("12:00 AM", display(0, 0));
+    }
+
+    #[test]
+    fn early_morning_is_am() {
+        assert_eq!("1:05 AM", display(1, 5));
+    }
+
+    #[test]
+    fn last_morning_hour_is_am() {
+        assert_eq!("11:59 AM", display(11, 59));
+    }
+
+    #[test]
+    fn noon_is_12_pm() {
+        assert_eq!("12:00 PM", display(12, 0));
+    }
+
+    #[test]
+    fn early_afternoon_is_pm() {
+        assert_eq!("1:30 PM", display(13, 30));
+    }
+
+    #[test]
+    fn last_hour_is_pm() {
+        assert_eq!("11:45 PM", display(23, 45));
+    }
+
+    #[test]
+    fn minutes_until_wraps_around_midnight() {
+        let before_midnight = GameTime {
+            hour: 23,
+            minute: 50,
+        };
+        let after_midnight = GameTime { hour: 0, minute: 5 };
+
+        assert_eq!(15, before_midnight.minutes_until(&after_midnight));
+        assert_eq!(1425, after_midnight.minutes_until(&before_midnight));
+        assert_eq!(0, after_midnight.minutes_until(&after_midnight));
+    }
+
+    #[test]
+    fn minutes_past_counts_from_the_start_of_the_run() {
+        let after_midnight = GameTime { hour: 0, minute: 5 };
+
+        assert_eq!(
+            15 * 60 + 5,
+            after_midnight.minutes_past(&WAKE_GOAL),
+            "the clock going past midnight should still be past the wake goal"
+        );
+        assert_eq!(-(15 * 60 + 5), WAKE_GOAL.minutes_past(&after_midnight));
+        assert_eq!(0, WAKE_GOAL.minutes_past(&WAKE_GOAL));
+    }
+}
+
+pub struct FadeEvent(pub FadeDirection);
 
-enum FadeDirection {
+pub enum FadeDirection {
     In,
     Out,
 }
 
-struct SnoozeEvent;
+pub struct SnoozeEvent;
 
-/// Sets up the main game screen.
-fn game_setup(
-    mut commands: Commands,
-    image_assets: Res<ImageAssets>,
-    font_assets: Res<FontAssets>,
-    time: Res<GameTime>,
-    mut event_writer: EventWriter<FadeEvent>,
-) {
-    // spawn overlay
-    commands
-        .spawn_bundle(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                ..Default::default()
-            },
-            color: OVERLAY_COLOR.into(),
-            ..Default::default()
-        })
-        .insert(GameComponent)
+/// Sent when the player presses somewhere other than the snooze or dismiss button, or knocks something over
+pub struct MissEvent;
+
+/// Sent when something worth knowing about happens during a run
+pub enum GameLogEvent {
+    /// The player got the snooze button with the provided finger
+    SnoozePressed(Finger),
+    /// The player pressed somewhere that wasn't a button
+    Missed,
+    /// The player bumped the hand into a hazard
+    KnockedSomethingOver,
+    /// A snooze moved the clock forward to the provided time
+    TimeAdvanced(GameTime),
+}
+
+/// Sent when something happens that loses the run
+pub struct GameOverEvent {
+    pub reason: GameOverReason,
+}
+
+/// Sent once a run has finished playing out, with how it went
+pub struct GameResultEvent(pub GameResult);
+
+/// How the most recent run went, if one has finished since the game started
+pub struct LastGameResult(pub Option<GameResult>);
+
+/// How a run went, once it's over
+#[derive(Clone, Copy, Debug)]
+pub struct GameResult {
+    pub final_time: GameTime,
+    pub snoozes: u32,
+    pub mode: GameMode,
+    pub difficulty: Difficulty,
+    pub reason: GameOverReason,
+    /// Whether the player got up on purpose rather than losing
+    pub won: bool,
+    /// How many seconds the run took, not counting time spent paused
+    pub real_seconds: f32,
+    pub wake_goal: GameTime,
+    pub seed: u64,
+}
+
+impl GameResult {
+    /// The score for this run
+    pub fn score(&self) -> Score {
+        Score {
+            snoozes: self.snoozes,
+            time: self.final_time,
+        }
+    }
+
+    /// How many minutes past the wake goal the run lasted, if it was a night owl run
+    pub fn minutes_past_goal(&self) -> Option<u32> {
+        match self.mode {
+            GameMode::NightOwl => Some(self.final_time.minutes_past(&self.wake_goal).max(0) as u32),
+            GameMode::Classic | GameMode::Weekend | GameMode::Workday => None,
+        }
+    }
+}
+
+/// Keeps the arm from being controlled at the start of a run until its joints exist and have had a moment to settle
+struct JointSettling {
+    /// Whether the joints for both the arm and the hand have been built yet
+    joints_ready: bool,
+    timer: Timer,
+}
+
+impl Default for JointSettling {
+    fn default() -> Self {
+        JointSettling {
+            joints_ready: false,
+            timer: Timer::from_seconds(JOINT_SETTLING_SECONDS, false),
+        }
+    }
+}
+
+impl JointSettling {
+    /// Whether the arm is ready to be controlled
+    fn is_settled(&self) -> bool {
+        self.joints_ready && self.timer.finished()
+    }
+}
+
+/// Snoozes the alarm for players who are having trouble doing it themselves, once per run
+struct SnoozeAssist {
+    /// Whether the game will still snooze for the player this run
+    available: bool,
+    /// Whether the game just snoozed for the player, so that snooze doesn't count as one they did themselves
+    assisting: bool,
+    /// How long the current alarm has been going without being snoozed
+    timer: Timer,
+}
+
+/// The message letting the player know the game snoozed for them, and how much longer it stays up
+#[derive(Component)]
+struct SnoozeAssistMessage(Timer);
+
+/// The message letting the player know they missed, up for as long as the miss penalty lasts
+#[derive(Component)]
+struct MissIndicator;
+
+/// How long the player has left the alarm going without doing anything
+#[derive(Default)]
+pub struct Idle {
+    seconds: f32,
+    /// How long the player can be idle before the alarm starts escalating
+    timeout: f32,
+    /// How long the player can be idle before the run ends, if there's a limit
+    loss_seconds: Option<f32>,
+}
+
+impl Idle {
+    /// How insistent the alarm is getting from being ignored, from 0 for not at all to 1 for as much as it gets
+    pub fn escalation(&self) -> f32 {
+        ((self.seconds - self.timeout) / IDLE_ESCALATION_RAMP_SECONDS).clamp(0.0, 1.0)
+    }
+}
+
+/// Counts down to starting a new run when auto retry is on
+struct AutoRetryTimer(Option<Timer>);
+
+/// Applies the tuning values for the selected difficulty and game mode.
+fn tuning_setup(
+    difficulty: Res<Difficulty>,
+    game_mode: Res<GameMode>,
+    mut reach: ResMut<Reach>,
+    mut wake_goal: ResMut<WakeGoal>,
+    mut bounds: ResMut<Bounds>,
+    mut miss_penalty: ResMut<MissPenalty>,
+    mut gravity: ResMut<Gravity>,
+    mut theme: ResMut<Theme>,
+    mut miss_limit: ResMut<MissLimit>,
+    mut miss_count: ResMut<MissCount>,
+    mut snooze_reward: ResMut<SnoozeReward>,
+    mut snooze_streak: ResMut<SnoozeStreak>,
+    mut snooze_button_layout: ResMut<SnoozeButtonLayout>,
+    mut vibration_pattern: ResMut<VibrationPattern>,
+    mut notifications: ResMut<Notifications>,
+    mut shake_meter: ResMut<ShakeMeter>,
+) {
+    shake_meter.amount = 0.0;
+    shake_meter.limit = difficulty.shake_limit();
+    snooze_button_layout.count = difficulty.snooze_button_count();
+    snooze_button_layout.scale = difficulty.snooze_button_scale();
+    notifications.enabled = game_mode.has_notifications();
+    notifications.timer = Timer::from_seconds(MAX_NOTIFICATION_DELAY_SECONDS, false);
+    *vibration_pattern = game_mode.vibration_pattern();
+    *reach = difficulty.reach();
+    *snooze_reward = game_mode.snooze_reward();
+    snooze_streak.0 = 0;
+    miss_limit.0 = difficulty.miss_limit();
+    miss_count.0 = 0;
+    *theme = game_mode.theme();
+    gravity.0 = difficulty.gravity();
+    wake_goal.0 = game_mode.wake_goal();
+    bounds.table = game_mode.table_bounds();
+    miss_penalty.0 = game_mode.miss_penalty_seconds();
+}
+
+/// Sets up the main game screen.
+fn game_setup(
+    mut commands: Commands,
+    image_assets: Res<ImageAssets>,
+    font_assets: Res<FontAssets>,
+    scene_assets: Res<SceneAssets>,
+    scene_configs: Res<Assets<SceneConfig>>,
+    time: Res<GameTime>,
+    save_data: Res<SaveData>,
+    mut rng: ResMut<GameRng>,
+    mut starting_pose: ResMut<StartingPose>,
+    mut rest_positions: ResMut<RestPositions>,
+    reach: Res<Reach>,
+    theme: Res<Theme>,
+    seed_input: Res<SeedInput>,
+    localization: Res<Localization>,
+    mut snooze_button_layout: ResMut<SnoozeButtonLayout>,
+    mut event_writer: EventWriter<FadeEvent>,
+) {
+    let scene = scene_configs
+        .get(&scene_assets.bedroom)
+        .expect("scene config should be loaded");
+
+    // pick how the arm starts out
+    *rng = GameRng::new(seed_input.seed().unwrap_or_else(rand::random));
+    *starting_pose = if save_data.settings.randomize_starting_pose {
+        STARTING_POSES[rng.rng.gen_range(0..STARTING_POSES.len())]
+    } else {
+        STARTING_POSES[0]
+    };
+    snooze_button_layout.active = rng.rng.gen_range(0..snooze_button_layout.count.max(1));
+    let pose_offset = Vec3::new(
+        starting_pose.arm_anchor_x - STARTING_POSES[0].arm_anchor_x,
+        starting_pose.arm_anchor_y - STARTING_POSES[0].arm_anchor_y,
+        0.0,
+    );
+
+    // spawn overlay
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                ..Default::default()
+            },
+            color: theme.overlay_color.into(),
+            ..Default::default()
+        })
+        .insert(GameComponent)
         .insert(Overlay);
 
     // spawn background
-    let background_position = Vec3::new(0.0, 0.0, 0.0);
-    let background_scale = Vec3::ONE;
+    let background_position = Vec3::from(scene.background.position);
+    let background_scale = Vec3::from(scene.background.scale);
     commands
         .spawn_bundle(SpriteBundle {
             texture: image_assets.background.clone(),
@@ -267,11 +1435,15 @@ fn game_setup(
         .insert(GameComponent);
 
     // spawn phone
-    let phone_position = Vec3::new(0.0, 0.0, 1.0);
-    let phone_scale = Vec3::new(0.5, 0.5, 1.0);
+    let phone_position = Vec3::from(scene.phone.position);
+    let phone_scale = Vec3::from(scene.phone.scale);
     commands
         .spawn_bundle(SpriteBundle {
             texture: image_assets.phone.clone(),
+            sprite: Sprite {
+                color: save_data.settings.phone_skin.tint(),
+                ..Default::default()
+            },
             transform: Transform {
                 translation: phone_position,
                 scale: phone_scale,
@@ -289,8 +1461,8 @@ fn game_setup(
                         time.to_string(),
                         TextStyle {
                             font: font_assets.main.clone(),
-                            font_size: 100.0,
-                            color: Color::WHITE,
+                            font_size: scene.time_display.font_size,
+                            color: config_color(scene.time_display.color),
                         },
                         TextAlignment {
                             horizontal: HorizontalAlign::Center,
@@ -298,7 +1470,7 @@ fn game_setup(
                         },
                     ),
                     transform: Transform {
-                        translation: Vec3::new(0.0, 300.0, 1.0),
+                        translation: Vec3::from(scene.time_display.position),
                         scale: Vec3::new(1.0, 1.0, 1.0),
                         ..Default::default()
                     },
@@ -306,50 +1478,145 @@ fn game_setup(
                 })
                 .insert(TimeDisplay);
 
-            // snooze button
+            // snooze counter, just below the time display
+            let mut snooze_counter_position = Vec3::from(scene.time_display.position);
+            snooze_counter_position.y -= SNOOZE_COUNTER_OFFSET_Y;
+            parent
+                .spawn_bundle(Text2dBundle {
+                    text: Text::with_section(
+                        snooze_counter_text(0, &localization),
+                        TextStyle {
+                            font: font_assets.main.clone(),
+                            font_size: scene.time_display.font_size * SNOOZE_COUNTER_FONT_SCALE,
+                            color: config_color(scene.time_display.color),
+                        },
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        },
+                    ),
+                    transform: Transform::from_translation(snooze_counter_position),
+                    ..Default::default()
+                })
+                .insert(SnoozeCounter);
+
+            // screen glow, just behind the time display
+            let mut glow_position = Vec3::from(scene.time_display.position);
+            glow_position.z -= SCREEN_GLOW_Z_OFFSET;
             parent
                 .spawn_bundle(SpriteBundle {
                     sprite: Sprite {
-                        color: Color::RED,
-                        custom_size: Some(Vec2::new(250.0, 100.0)),
-                        ..Default::default()
-                    },
-                    transform: Transform {
-                        translation: Vec3::new(0.0, -200.0, 1.0),
-                        scale: Vec3::new(1.0, 1.0, 1.0),
+                        color: Color::NONE,
+                        custom_size: Some(Vec2::new(SCREEN_GLOW_WIDTH, SCREEN_GLOW_HEIGHT)),
                         ..Default::default()
                     },
+                    transform: Transform::from_translation(glow_position),
                     ..Default::default()
                 })
-                .insert(SnoozeButton)
-                .with_children(|parent| {
-                    parent.spawn_bundle(Text2dBundle {
-                        text: Text::with_section(
-                            "SNOOZE",
-                            TextStyle {
-                                font: font_assets.main.clone(),
-                                font_size: 60.0,
-                                color: Color::BLACK,
-                            },
-                            TextAlignment {
-                                horizontal: HorizontalAlign::Center,
-                                vertical: VerticalAlign::Center,
-                            },
-                        ),
+                .insert(ScreenGlow::default());
+
+            // snooze buttons, side by side
+            let button_count = snooze_button_layout.count.max(1);
+            let full_size = Vec2::from(scene.snooze_button.size) * snooze_button_layout.scale;
+            let button_size = Vec2::new(
+                (full_size.x - SNOOZE_BUTTON_GAP * (button_count - 1) as f32) / button_count as f32,
+                full_size.y,
+            );
+            let first_button_x =
+                scene.snooze_button.position[0] - (full_size.x - button_size.x) / 2.0;
+            for index in 0..button_count {
+                let mut button_position = Vec3::from(scene.snooze_button.position);
+                button_position.x =
+                    first_button_x + (button_size.x + SNOOZE_BUTTON_GAP) * index as f32;
+                parent
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: snooze_button_color(
+                                config_color(scene.snooze_button.color),
+                                index == snooze_button_layout.active,
+                            ),
+                            custom_size: Some(button_size),
+                            ..Default::default()
+                        },
                         transform: Transform {
-                            translation: Vec3::new(0.0, 0.0, 1.0),
+                            translation: button_position,
                             scale: Vec3::new(1.0, 1.0, 1.0),
                             ..Default::default()
                         },
                         ..Default::default()
+                    })
+                    .insert(SnoozeButton(index))
+                    .with_children(|parent| {
+                        parent.spawn_bundle(Text2dBundle {
+                            text: Text::with_section(
+                                localization.localize("hud.snooze_button"),
+                                TextStyle {
+                                    font: font_assets.main.clone(),
+                                    // shrink the label to fit narrower or smaller buttons, but don't blow it up on bigger ones
+                                    font_size: scene.snooze_button.text.font_size
+                                        * snooze_button_layout.scale.min(1.0)
+                                        / button_count as f32,
+                                    color: config_color(scene.snooze_button.text.color),
+                                },
+                                TextAlignment {
+                                    horizontal: HorizontalAlign::Center,
+                                    vertical: VerticalAlign::Center,
+                                },
+                            ),
+                            transform: Transform {
+                                translation: Vec3::from(scene.snooze_button.text.position),
+                                scale: Vec3::new(1.0, 1.0, 1.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        });
                     });
-                });
+            }
+
+            // dismiss button, for getting up instead of snoozing
+            let dismiss_button = scene
+                .dismiss_button
+                .as_ref()
+                .filter(|_| save_data.settings.dismiss_button);
+            if let Some(dismiss_button) = dismiss_button {
+                parent
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: config_color(dismiss_button.color),
+                            custom_size: Some(Vec2::from(dismiss_button.size)),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(Vec3::from(dismiss_button.position)),
+                        ..Default::default()
+                    })
+                    .insert(DismissButton)
+                    .with_children(|parent| {
+                        parent.spawn_bundle(Text2dBundle {
+                            text: Text::with_section(
+                                localization.localize("hud.dismiss_button"),
+                                TextStyle {
+                                    font: font_assets.main.clone(),
+                                    font_size: dismiss_button.text.font_size,
+                                    color: config_color(dismiss_button.text.color),
+                                },
+                                TextAlignment {
+                                    horizontal: HorizontalAlign::Center,
+                                    vertical: VerticalAlign::Center,
+                                },
+                            ),
+                            transform: Transform::from_translation(Vec3::from(
+                                dismiss_button.text.position,
+                            )),
+                            ..Default::default()
+                        });
+                    });
+            }
         });
 
     // spawn arm anchor
     let arm_anchor_position = Vec3::new(
-        ARM_ANCHOR_STARTING_POSITION_X,
-        ARM_ANCHOR_STARTING_POSITION_Y,
+        starting_pose.arm_anchor_x,
+        starting_pose.arm_anchor_y,
         ARM_ANCHOR_STARTING_POSITION_Z,
     );
     let arm_anchor = commands
@@ -383,15 +1650,14 @@ fn game_setup(
             ..Default::default()
         })
         .insert(ColliderPositionSync::Discrete)
-        //TODO .insert(ColliderDebugRender::with_id(0))
         .insert(GameComponent)
         .insert(ArmAnchor)
         .id();
 
     // spawn arm
-    let arm_position = Vec3::new(500.0, 0.0, 10.0);
-    let arm_rotation = Quat::from_rotation_z(-0.79);
-    let arm_scale = Vec3::ONE;
+    let arm_position = Vec3::from(scene.arm.position) + pose_offset;
+    let arm_rotation = Quat::from_rotation_z(starting_pose.arm_angle);
+    let arm_scale = Vec3::from(scene.arm.scale);
     let arm = commands
         .spawn_bundle(SpriteBundle {
             texture: image_assets.arm.clone(),
@@ -412,21 +1678,24 @@ fn game_setup(
             ..Default::default()
         })
         .insert_bundle(ColliderBundle {
-            shape: ColliderShape::ball(100.0).into(),
+            shape: ColliderShape::ball(scene.arm.collider_radius).into(),
             collider_type: ColliderType::Sensor.into(),
             mass_properties: ColliderMassProps::Density(1.0).into(),
             ..Default::default()
         })
         .insert(ColliderPositionSync::Discrete)
-        //TODO .insert(ColliderDebugRender::with_id(1))
         .insert(GameComponent)
         .insert(Arm)
         .id();
 
     // spawn hand
-    let hand_position = Vec3::new(0.0, 0.0, 11.0);
-    let hand_rotation = Quat::from_rotation_z(0.0);
-    let hand_scale = Vec3::ONE;
+    let hand_position = Vec3::from(scene.hand.position) + pose_offset;
+    let hand_rotation = Quat::from_rotation_z(starting_pose.hand_angle);
+    *rest_positions = RestPositions {
+        arm: arm_position,
+        hand: hand_position,
+    };
+    let hand_scale = Vec3::from(scene.hand.scale);
     let hand = commands
         .spawn_bundle(SpriteBundle {
             texture: image_assets.hand.clone(),
@@ -447,101 +1716,49 @@ fn game_setup(
             ..Default::default()
         })
         .insert_bundle(ColliderBundle {
-            shape: ColliderShape::ball(100.0).into(),
+            shape: ColliderShape::ball(scene.hand.collider_radius).into(),
             mass_properties: ColliderMassProps::Density(1.0).into(),
             ..Default::default()
         })
         .insert(ColliderPositionSync::Discrete)
-        //TODO .insert(ColliderDebugRender::with_id(2))
         .insert(GameComponent)
         .insert(Hand)
         .with_children(|parent| {
-            // thumb
-            parent
-                .spawn_bundle(SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::NONE,
-                        ..Default::default()
-                    },
-                    transform: Transform {
-                        translation: Vec3::new(-170.0, -45.0, 1.0),
-                        scale: Vec3::new(30.0, 25.0, 1.0),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(TouchArea);
-
-            // index finger
-            parent
-                .spawn_bundle(SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::NONE,
-                        ..Default::default()
-                    },
-                    transform: Transform {
-                        translation: Vec3::new(-160.0, 80.0, 1.0),
-                        scale: Vec3::new(30.0, 25.0, 1.0),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(TouchArea);
-
-            // middle finger
-            parent
-                .spawn_bundle(SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::NONE,
-                        ..Default::default()
-                    },
-                    transform: Transform {
-                        translation: Vec3::new(-135.0, 138.0, 1.0),
-                        scale: Vec3::new(30.0, 25.0, 1.0),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(TouchArea);
-
-            // ring finger
-            parent
-                .spawn_bundle(SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::NONE,
-                        ..Default::default()
-                    },
-                    transform: Transform {
-                        translation: Vec3::new(-42.0, 155.0, 1.0),
-                        scale: Vec3::new(30.0, 25.0, 1.0),
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(TouchArea);
-
-            // pinky
-            parent
-                .spawn_bundle(SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::NONE,
-                        ..Default::default()
-                    },
-                    transform: Transform {
-                        translation: Vec3::new(60.0, 140.0, 1.0),
-                        scale: Vec3::new(27.0, 22.0, 1.0),
+            for touch_area in &scene.touch_areas {
+                parent
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::NONE,
+                            ..Default::default()
+                        },
+                        transform: Transform {
+                            translation: Vec3::from(touch_area.position),
+                            scale: (Vec2::from(touch_area.size) * reach.touch_area_scale)
+                                .extend(1.0),
+                            ..Default::default()
+                        },
                         ..Default::default()
-                    },
-                    ..Default::default()
-                })
-                .insert(TouchArea);
+                    })
+                    .insert(TouchArea)
+                    .insert(touch_area.finger);
+            }
         })
         .id();
 
+    if DEV_MODE {
+        commands
+            .entity(arm_anchor)
+            .insert(ColliderDebugRender::with_id(0));
+        commands.entity(arm).insert(ColliderDebugRender::with_id(1));
+        commands
+            .entity(hand)
+            .insert(ColliderDebugRender::with_id(2));
+    }
+
     // attach arm to arm anchor
     let arm_joint = RevoluteJoint::new()
-        .local_anchor1(point![-50.0, 0.0])
-        .local_anchor2(point![300.0, -250.0])
+        .local_anchor1(scene.arm_joint.anchor1.into())
+        .local_anchor2(scene.arm_joint.anchor2.into())
         .motor_model(MotorModel::VelocityBased)
         .motor_velocity(0.0, ARM_MOTOR_FACTOR);
     commands
@@ -550,8 +1767,8 @@ fn game_setup(
 
     // attach hand to arm
     let hand_joint = RevoluteJoint::new()
-        .local_anchor1(point![-300.0, 250.0])
-        .local_anchor2(point![130.0, -120.0])
+        .local_anchor1(scene.hand_joint.anchor1.into())
+        .local_anchor2(scene.hand_joint.anchor2.into())
         .motor_model(MotorModel::VelocityBased)
         .motor_velocity(0.0, HAND_MOTOR_FACTOR);
     commands
@@ -561,97 +1778,427 @@ fn game_setup(
     event_writer.send(FadeEvent(FadeDirection::In));
 }
 
-/// Handles fading in and out
-fn fade_system(
+/// Sets up the stuff on the table that the hand has to get around, if there is any on this difficulty
+fn obstacle_setup(
     mut commands: Commands,
-    mut events: EventReader<FadeEvent>,
-    query: Query<Entity, With<Overlay>>,
+    scene_assets: Res<SceneAssets>,
+    scene_configs: Res<Assets<SceneConfig>>,
+    difficulty: Res<Difficulty>,
 ) {
-    for event in events.iter() {
-        for entity in query.iter() {
-            match event.0 {
-                FadeDirection::In => fade_ui_color(
-                    &mut commands,
-                    entity,
-                    OVERLAY_COLOR,
-                    Color::NONE,
-                    FADE_IN_TIME,
-                    FADE_IN_TWEEN_COMPLETED,
-                ),
-                FadeDirection::Out => fade_ui_color(
-                    &mut commands,
-                    entity,
-                    Color::NONE,
-                    OVERLAY_COLOR,
-                    FADE_OUT_TIME,
-                    FADE_OUT_TWEEN_COMPLETED,
-                ),
-            }
-        }
+    if !difficulty.has_table_clutter() {
+        return;
     }
-}
 
-struct UiColorLens {
-    start: UiColor,
-    end: UiColor,
-}
+    let scene = scene_configs
+        .get(&scene_assets.bedroom)
+        .expect("scene config should be loaded");
 
-impl Lens<UiColor> for UiColorLens {
-    fn lerp(&mut self, target: &mut UiColor, ratio: f32) {
-        // copied from SpriteColorLens
-        // Note: Add<f32> for Color affects alpha, but not Mul<f32>. So use Vec4 for consistency.
-        let start: Vec4 = self.start.0.into();
-        let end: Vec4 = self.end.0.into();
-        let value = start.lerp(end, ratio);
-        target.0 = value.into();
+    for obstacle in &scene.obstacles {
+        let position = Vec3::from(obstacle.position);
+        let size = Vec2::from(obstacle.size);
+        let mut entity_commands = commands.spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: config_color(obstacle.color),
+                custom_size: Some(size),
+                ..Default::default()
+            },
+            transform: Transform::from_translation(position),
+            ..Default::default()
+        });
+        entity_commands
+            .insert_bundle(ColliderBundle {
+                shape: ColliderShape::cuboid(size.x / 2.0, size.y / 2.0).into(),
+                position: ColliderPosition(Isometry::new(Vector::new(position.x, position.y), 0.0))
+                    .into(),
+                flags: ColliderFlags {
+                    active_events: ActiveEvents::CONTACT_EVENTS,
+                    ..Default::default()
+                }
+                .into(),
+                ..Default::default()
+            })
+            .insert(GameComponent)
+            .insert(Obstacle)
+            .insert(Name::new(obstacle.name.clone()));
+        if obstacle.hazard {
+            entity_commands.insert(Hazard);
+        }
     }
 }
 
-/// Fades the `UiColor` of an entity
-fn fade_ui_color(
-    commands: &mut Commands,
-    entity: Entity,
-    start_color: Color,
-    end_color: Color,
-    duration: Duration,
-    user_data: u64,
+/// Handles penalizing the player for bumping the hand into a hazard, the same as a miss
+fn hazard_system(
+    mut snooze_streak: ResMut<SnoozeStreak>,
+    save_data: Res<SaveData>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    mut contact_events: EventReader<ContactEvent>,
+    mut miss_events: EventWriter<MissEvent>,
+    mut log_events: EventWriter<GameLogEvent>,
+    hand_query: Query<(), With<Hand>>,
+    hazard_query: Query<(), With<Hazard>>,
 ) {
-    let tween = Tween::new(
-        EaseFunction::SineInOut,
-        TweeningType::Once,
-        duration,
-        UiColorLens {
-            start: start_color.into(),
-            end: end_color.into(),
-        },
-    )
-    .with_completed_event(true, user_data);
-    commands.entity(entity).insert(Animator::new(tween));
+    for event in contact_events.iter() {
+        let (collider1, collider2) = match event {
+            ContactEvent::Started(collider1, collider2) => (collider1.entity(), collider2.entity()),
+            ContactEvent::Stopped(_, _) => continue,
+        };
+
+        let hand_hit_hazard = (hand_query.get(collider1).is_ok()
+            && hazard_query.get(collider2).is_ok())
+            || (hand_query.get(collider2).is_ok() && hazard_query.get(collider1).is_ok());
+        if !hand_hit_hazard {
+            continue;
+        }
+
+        // whoops
+        log_events.send(GameLogEvent::KnockedSomethingOver);
+        play_sfx(&audio, asset_server.load(DROP_SOUND), &save_data.settings);
+        snooze_streak.0 = 0;
+        miss_events.send(MissEvent);
+    }
 }
 
-/// Handles rotating the hand
-fn hand_rotation_system(
-    input_allowed: Res<InputAllowed>,
-    keyboard: Res<Input<KeyCode>>,
-    mut joint_set: ResMut<ImpulseJointSet>,
-    mut query: Query<(&JointHandleComponent, &mut RigidBodyActivationComponent), With<Hand>>,
+/// Handles shaking the screen when the hand or arm hits something, harder the faster they hit each other
+fn collision_shake_system(
+    save_data: Res<SaveData>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut contact_events: EventReader<ContactEvent>,
+    arm_part_query: Query<(), Or<(With<Hand>, With<Arm>)>>,
+    velocity_query: Query<&RigidBodyVelocityComponent>,
 ) {
-    for (joint_handle, mut activation) in query.iter_mut() {
-        let joint = joint_set
-            .get_mut(joint_handle.handle())
-            .expect("couldn't find joint");
-        activation.wake_up(true);
+    for event in contact_events.iter() {
+        if save_data.settings.reduce_motion {
+            continue;
+        }
 
-        if keyboard.pressed(ROTATE_HAND_DOWN_KEY) && input_allowed.0 {
-            joint.data =
-                joint
-                    .data
-                    .motor_velocity(JointAxis::AngX, HAND_CONTROL_POWER, HAND_MOTOR_FACTOR);
-        } else if keyboard.pressed(ROTATE_HAND_UP_KEY) && input_allowed.0 {
-            joint.data =
-                joint
-                    .data
-                    .motor_velocity(JointAxis::AngX, -HAND_CONTROL_POWER, HAND_MOTOR_FACTOR);
+        let (entity1, entity2) = match event {
+            ContactEvent::Started(collider1, collider2) => (collider1.entity(), collider2.entity()),
+            ContactEvent::Stopped(_, _) => continue,
+        };
+        if arm_part_query.get(entity1).is_err() && arm_part_query.get(entity2).is_err() {
+            continue;
+        }
+
+        // anything without a velocity isn't going anywhere
+        let velocity = |entity| {
+            velocity_query.get(entity).map_or(Vec2::ZERO, |velocity| {
+                Vec2::new(velocity.linvel.x, velocity.linvel.y)
+            })
+        };
+        let impact_speed = (velocity(entity1) - velocity(entity2)).length();
+        let strength = (impact_speed - COLLISION_SHAKE_MIN_SPEED)
+            / (COLLISION_SHAKE_MAX_SPEED - COLLISION_SHAKE_MIN_SPEED);
+        if strength > 0.0 {
+            camera_shake.0 = camera_shake
+                .0
+                .max(strength.min(1.0) * COLLISION_SHAKE_MAX_OFFSET);
+        }
+    }
+}
+
+/// Handles moving the camera around while it's being shaken, and settling it back in the middle once it's done
+fn camera_shake_system(
+    time: Res<Time>,
+    death_sequence: Res<DeathSequence>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    if camera_shake.0 <= 0.0 || death_sequence.is_started() {
+        // the death sequence has its own plans for the camera
+        return;
+    }
+
+    camera_shake.0 = (camera_shake.0 - COLLISION_SHAKE_DECAY * time.delta_seconds()).max(0.0);
+    // just for looks, so it doesn't use up any of the run's randomness
+    let offset = Vec2::new(
+        rand::random::<f32>() * 2.0 - 1.0,
+        rand::random::<f32>() * 2.0 - 1.0,
+    ) * camera_shake.0;
+    for mut transform in camera_query.iter_mut() {
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+    }
+}
+
+/// Sets up the hints about which way the arm extends
+fn arm_hint_setup(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+    mut arm_hint_timer: ResMut<ArmHintTimer>,
+) {
+    arm_hint_timer.0.reset();
+    let key_bindings = &save_data.settings.key_bindings;
+    let hint_style = TextStyle {
+        font: font_assets.main.clone(),
+        font_size: 25.0 * save_data.settings.ui_scale(),
+        color: Color::NONE,
+    };
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(20.0),
+                    top: Val::Percent(45.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text {
+                        sections: vec![
+                            TextSection {
+                                value: localization.localize_with(
+                                    "hud.hint_extend",
+                                    &[("key", format!("{:?}", key_bindings.extend_arm))],
+                                ),
+                                style: hint_style.clone(),
+                            },
+                            TextSection {
+                                value: localization.localize_with(
+                                    "hud.hint_retract",
+                                    &[("key", format!("{:?}", key_bindings.retract_arm))],
+                                ),
+                                style: hint_style.clone(),
+                            },
+                        ],
+                        alignment: TextAlignment {
+                            horizontal: HorizontalAlign::Right,
+                            ..Default::default()
+                        },
+                    },
+                    ..Default::default()
+                })
+                .insert(ArmHint);
+        });
+}
+
+/// Sets up the markers showing where the fingers are relative to the snooze button, if the player wants them
+fn finger_hint_setup(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    save_data: Res<SaveData>,
+) {
+    if !save_data.settings.finger_hints {
+        return;
+    }
+
+    commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: FINGER_HINT_VALID_COLOR,
+                custom_size: Some(Vec2::new(FINGER_HINT_MARKER_SIZE, FINGER_HINT_MARKER_SIZE)),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, FINGER_HINT_Z),
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(FingerHintMarker);
+
+    commands
+        .spawn_bundle(Text2dBundle {
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 20.0 * save_data.settings.ui_scale(),
+                    color: Color::WHITE,
+                },
+                TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    vertical: VerticalAlign::Center,
+                },
+            ),
+            transform: Transform::from_xyz(0.0, 0.0, FINGER_HINT_Z),
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(FingerHintText);
+}
+
+/// Handles keeping the finger markers up to date with where the fingers are
+fn finger_hint_system(
+    finger_contact: Res<FingerContact>,
+    localization: Res<Localization>,
+    valid_press_position: Res<ValidPressPosition>,
+    snooze_button_layout: Res<SnoozeButtonLayout>,
+    snooze_button_query: Query<(&GlobalTransform, &SnoozeButton)>,
+    mut marker_query: Query<(&mut Transform, &mut Visibility, &mut Sprite), With<FingerHintMarker>>,
+    mut text_query: Query<
+        (&mut Transform, &mut Text),
+        (With<FingerHintText>, Without<FingerHintMarker>),
+    >,
+) {
+    if !finger_contact.is_changed() {
+        return;
+    }
+
+    for (mut transform, mut visibility, mut sprite) in marker_query.iter_mut() {
+        match finger_contact.contact_point {
+            Some(contact_point) => {
+                transform.translation = contact_point.extend(FINGER_HINT_Z);
+                visibility.is_visible = true;
+                sprite.color = if valid_press_position.0.is_some() {
+                    FINGER_HINT_VALID_COLOR
+                } else {
+                    FINGER_HINT_INVALID_COLOR
+                };
+            }
+            None => visibility.is_visible = false,
+        }
+    }
+
+    let button_position = match snooze_button_query
+        .iter()
+        .find(|(_, button)| button.0 == snooze_button_layout.active)
+    {
+        Some((button_transform, _)) => button_transform.translation.truncate(),
+        None => return,
+    };
+
+    for (mut transform, mut text) in text_query.iter_mut() {
+        transform.translation = Vec3::new(
+            button_position.x,
+            button_position.y + FINGER_HINT_TEXT_OFFSET_Y,
+            FINGER_HINT_Z,
+        );
+        text.sections[0].value = match (
+            finger_contact.contact_point,
+            finger_contact.nearest_distance,
+        ) {
+            (Some(_), _) => String::new(),
+            (None, Some(distance)) => localization.localize_with(
+                "hud.finger_distance",
+                &[("distance", format!("{distance:.0}"))],
+            ),
+            (None, None) => String::new(),
+        };
+    }
+}
+
+/// Handles fading in and out
+fn fade_system(
+    mut commands: Commands,
+    mut events: EventReader<FadeEvent>,
+    theme: Res<Theme>,
+    tween_easing: Res<TweenEasing>,
+    query: Query<Entity, With<Overlay>>,
+) {
+    // fade the alpha on its own so the color doesn't get muddied along the way
+    let transparent_overlay_color = with_alpha(theme.overlay_color, 0.0);
+    for event in events.iter() {
+        for entity in query.iter() {
+            match event.0 {
+                FadeDirection::In => fade_ui_color(
+                    &mut commands,
+                    entity,
+                    theme.overlay_color,
+                    transparent_overlay_color,
+                    FADE_IN_TIME,
+                    tween_easing.fade,
+                    FADE_IN_TWEEN_COMPLETED,
+                ),
+                FadeDirection::Out => fade_ui_color(
+                    &mut commands,
+                    entity,
+                    transparent_overlay_color,
+                    theme.overlay_color,
+                    FADE_OUT_TIME,
+                    tween_easing.fade,
+                    FADE_OUT_TWEEN_COMPLETED,
+                ),
+            }
+        }
+    }
+}
+
+struct UiColorLens {
+    start: UiColor,
+    end: UiColor,
+}
+
+impl Lens<UiColor> for UiColorLens {
+    fn lerp(&mut self, target: &mut UiColor, ratio: f32) {
+        // copied from SpriteColorLens
+        // Note: Add<f32> for Color affects alpha, but not Mul<f32>. So use Vec4 for consistency.
+        let start: Vec4 = self.start.0.into();
+        let end: Vec4 = self.end.0.into();
+        let value = start.lerp(end, ratio);
+        target.0 = value.into();
+    }
+}
+
+/// Fades the `UiColor` of an entity
+fn fade_ui_color(
+    commands: &mut Commands,
+    entity: Entity,
+    start_color: Color,
+    end_color: Color,
+    duration: Duration,
+    easing: EaseFunction,
+    user_data: u64,
+) {
+    let tween = Tween::new(
+        easing,
+        TweeningType::Once,
+        duration,
+        UiColorLens {
+            start: start_color.into(),
+            end: end_color.into(),
+        },
+    )
+    .with_completed_event(true, user_data);
+    commands.entity(entity).insert(Animator::new(tween));
+}
+
+/// Which way rotation controls turn things, depending on whether the player has inverted them
+fn rotation_direction(inverted: bool) -> f32 {
+    if inverted {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+/// Handles rotating the hand
+fn hand_rotation_system(
+    input_allowed: Res<InputAllowed>,
+    joint_settling: Res<JointSettling>,
+    action_state: Res<ActionState>,
+    save_data: Res<SaveData>,
+    mut joint_set: ResMut<ImpulseJointSet>,
+    mut query: Query<(&JointHandleComponent, &mut RigidBodyActivationComponent), With<Hand>>,
+) {
+    let input_allowed = input_allowed.0 && joint_settling.is_settled();
+    let power =
+        HAND_CONTROL_POWER * rotation_direction(save_data.settings.key_bindings.invert_hand);
+    for (joint_handle, mut activation) in query.iter_mut() {
+        let joint = match joint_set.get_mut(joint_handle.handle()) {
+            Some(joint) => joint,
+            // not in the physics world yet
+            None => continue,
+        };
+        activation.wake_up(true);
+
+        if action_state.pressed(InputAction::RotateHandDown) && input_allowed {
+            joint.data = joint
+                .data
+                .motor_velocity(JointAxis::AngX, power, HAND_MOTOR_FACTOR);
+        } else if action_state.pressed(InputAction::RotateHandUp) && input_allowed {
+            joint.data = joint
+                .data
+                .motor_velocity(JointAxis::AngX, -power, HAND_MOTOR_FACTOR);
         } else {
             joint.data = joint
                 .data
@@ -663,26 +2210,30 @@ fn hand_rotation_system(
 /// Handles rotating the arm
 fn arm_rotation_system(
     input_allowed: Res<InputAllowed>,
-    keyboard: Res<Input<KeyCode>>,
+    joint_settling: Res<JointSettling>,
+    action_state: Res<ActionState>,
+    save_data: Res<SaveData>,
     mut joint_set: ResMut<ImpulseJointSet>,
     mut query: Query<(&JointHandleComponent, &mut RigidBodyActivationComponent), With<Arm>>,
 ) {
+    let input_allowed = input_allowed.0 && joint_settling.is_settled();
+    let power = ARM_CONTROL_POWER * rotation_direction(save_data.settings.key_bindings.invert_arm);
     for (joint_handle, mut activation) in query.iter_mut() {
-        let joint = joint_set
-            .get_mut(joint_handle.handle())
-            .expect("couldn't find joint");
+        let joint = match joint_set.get_mut(joint_handle.handle()) {
+            Some(joint) => joint,
+            // not in the physics world yet
+            None => continue,
+        };
         activation.wake_up(true);
 
-        if keyboard.pressed(ROTATE_ARM_DOWN_KEY) && input_allowed.0 {
-            joint.data =
-                joint
-                    .data
-                    .motor_velocity(JointAxis::AngX, ARM_CONTROL_POWER, ARM_MOTOR_FACTOR);
-        } else if keyboard.pressed(ROTATE_ARM_UP_KEY) && input_allowed.0 {
-            joint.data =
-                joint
-                    .data
-                    .motor_velocity(JointAxis::AngX, -ARM_CONTROL_POWER, ARM_MOTOR_FACTOR);
+        if action_state.pressed(InputAction::RotateArmDown) && input_allowed {
+            joint.data = joint
+                .data
+                .motor_velocity(JointAxis::AngX, power, ARM_MOTOR_FACTOR);
+        } else if action_state.pressed(InputAction::RotateArmUp) && input_allowed {
+            joint.data = joint
+                .data
+                .motor_velocity(JointAxis::AngX, -power, ARM_MOTOR_FACTOR);
         } else {
             joint.data = joint
                 .data
@@ -691,10 +2242,198 @@ fn arm_rotation_system(
     }
 }
 
+/// Where the cursor is in the world, if it's in the window
+fn cursor_world_position(
+    windows: &Windows,
+    camera_query: &Query<(&GlobalTransform, &OrthographicProjection), With<MainCamera>>,
+) -> Option<Vec2> {
+    let window = windows.get_primary()?;
+    let cursor = window.cursor_position()?;
+    let (camera_transform, projection) = camera_query.get_single().ok()?;
+
+    // the camera looks at the middle of the window, zoomed out by the projection's scale
+    let window_size = Vec2::new(window.width(), window.height());
+    let offset = (cursor - window_size / 2.0) * projection.scale;
+    Some(camera_transform.mul_vec3(offset.extend(0.0)).truncate())
+}
+
+/// Handles steering the hand toward the cursor while the left mouse button is held, if mouse control is enabled.
+/// The arm rotates to line the hand up with the cursor, and extends or retracts to get it across, through the same
+/// actions the keys do. A quick click presses, the same as the press key.
+fn mouse_action_system(
+    time: Res<Time>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    save_data: Res<SaveData>,
+    mut action_state: ResMut<ActionState>,
+    mut held_seconds: Local<f32>,
+    camera_query: Query<(&GlobalTransform, &OrthographicProjection), With<MainCamera>>,
+    arm_anchor_query: Query<&GlobalTransform, With<ArmAnchor>>,
+    hand_query: Query<&GlobalTransform, With<Hand>>,
+) {
+    if !save_data.settings.mouse_control {
+        return;
+    }
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        *held_seconds = 0.0;
+    }
+    if mouse_buttons.just_released(MouseButton::Left) && *held_seconds < MOUSE_CLICK_SECONDS {
+        action_state.press(InputAction::Press);
+    }
+    if !mouse_buttons.pressed(MouseButton::Left) {
+        return;
+    }
+    *held_seconds += time.delta_seconds();
+
+    let cursor = match cursor_world_position(&windows, &camera_query) {
+        Some(cursor) => cursor,
+        None => return,
+    };
+    let (arm_anchor, hand) = match (arm_anchor_query.get_single(), hand_query.get_single()) {
+        (Ok(arm_anchor), Ok(hand)) => (
+            arm_anchor.translation.truncate(),
+            hand.translation.truncate(),
+        ),
+        // not in a run
+        _ => return,
+    };
+
+    // positive angles are counterclockwise, which is down for an arm reaching out to the left, unless the arm
+    // controls are turned around
+    let angle = (hand - arm_anchor).angle_between(cursor - arm_anchor)
+        * rotation_direction(save_data.settings.key_bindings.invert_arm);
+    if angle > MOUSE_STEER_ANGLE_DEADZONE {
+        action_state.press(InputAction::RotateArmDown);
+    } else if angle < -MOUSE_STEER_ANGLE_DEADZONE {
+        action_state.press(InputAction::RotateArmUp);
+    }
+
+    if cursor.x < hand.x - MOUSE_STEER_DISTANCE_DEADZONE {
+        action_state.press(InputAction::ExtendArm);
+    } else if cursor.x > hand.x + MOUSE_STEER_DISTANCE_DEADZONE {
+        action_state.press(InputAction::RetractArm);
+    }
+}
+
+/// Handles waiting for the arm's joints to be built and settle in before the arm can be controlled.
+/// Joints only get built from their `JointBuilderComponent` once the physics systems have run, which isn't until after the first frame of a run.
+fn joint_settling_system(
+    time: Res<Time>,
+    joint_set: Res<ImpulseJointSet>,
+    mut joint_settling: ResMut<JointSettling>,
+    hand_query: Query<&JointHandleComponent, With<Hand>>,
+    arm_query: Query<&JointHandleComponent, With<Arm>>,
+) {
+    if !joint_settling.joints_ready {
+        let joint_exists =
+            |joint_handle: &JointHandleComponent| joint_set.get(joint_handle.handle()).is_some();
+        joint_settling.joints_ready =
+            hand_query.iter().any(joint_exists) && arm_query.iter().any(joint_exists);
+        return;
+    }
+
+    joint_settling.timer.tick(time.delta());
+}
+
+#[cfg(test)]
+mod joint_settling_tests {
+    use super::*;
+
+    /// How many frames to give the joints to get built and settle in before giving up
+    const MAX_FRAMES: usize = 100;
+
+    /// Spawns bare versions of the arm anchor, arm, and hand, jointed together the same way `game_setup` does it
+    pub(super) fn spawn_arm(world: &mut World) -> (Entity, Entity) {
+        let arm_anchor = world
+            .spawn()
+            .insert_bundle(RigidBodyBundle {
+                body_type: RigidBodyType::KinematicVelocityBased.into(),
+                ..Default::default()
+            })
+            .insert(ArmAnchor)
+            .id();
+        let arm = world
+            .spawn()
+            .insert_bundle(RigidBodyBundle::default())
+            .insert(Arm)
+            .id();
+        let hand = world
+            .spawn()
+            .insert_bundle(RigidBodyBundle::default())
+            .insert(Hand)
+            .id();
+
+        world.entity_mut(arm).insert(JointBuilderComponent::new(
+            RevoluteJoint::new(),
+            arm_anchor,
+            arm,
+        ));
+        world
+            .entity_mut(hand)
+            .insert(JointBuilderComponent::new(RevoluteJoint::new(), arm, hand));
+
+        (arm, hand)
+    }
+
+    fn joint_exists(world: &World, entity: Entity) -> bool {
+        let joint_set = world.get_resource::<ImpulseJointSet>().unwrap();
+        world
+            .get::<JointHandleComponent>(entity)
+            .map_or(false, |joint_handle| {
+                joint_set.get(joint_handle.handle()).is_some()
+            })
+    }
+
+    #[test]
+    fn joints_exist_before_input_is_accepted() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
+            .insert_resource(JointSettling::default())
+            .add_system(joint_settling_system);
+        let (arm, hand) = spawn_arm(&mut app.world);
+
+        app.update();
+        assert!(
+            !app.world
+                .get_resource::<JointSettling>()
+                .unwrap()
+                .is_settled(),
+            "input was accepted on the first frame"
+        );
+
+        let mut settled = false;
+        for _ in 0..MAX_FRAMES {
+            // give the settling timer some real time to count down
+            std::thread::sleep(Duration::from_millis(5));
+            app.update();
+
+            settled = app
+                .world
+                .get_resource::<JointSettling>()
+                .unwrap()
+                .is_settled();
+            if settled {
+                assert!(joint_exists(&app.world, arm), "arm joint missing");
+                assert!(joint_exists(&app.world, hand), "hand joint missing");
+                break;
+            }
+        }
+
+        assert!(settled, "joints never settled");
+    }
+}
+
 /// Handles extending and retracting the arm
 fn arm_extension_system(
+    time: Res<Time>,
     input_allowed: Res<InputAllowed>,
-    keyboard: Res<Input<KeyCode>>,
+    reach: Res<Reach>,
+    bounds: Res<Bounds>,
+    action_state: Res<ActionState>,
+    save_data: Res<SaveData>,
+    mut arm_charge: ResMut<ArmCharge>,
     mut query: Query<
         (
             &mut RigidBodyVelocityComponent,
@@ -704,51 +2443,435 @@ fn arm_extension_system(
         With<ArmAnchor>,
     >,
 ) {
+    let charge_throw = save_data.settings.charge_throw;
+    if charge_throw {
+        if action_state.pressed(InputAction::ExtendArm) && input_allowed.0 {
+            // wind up
+            arm_charge.seconds += time.delta_seconds();
+            arm_charge.throw_speed = 0.0;
+        } else if arm_charge.seconds > 0.0 {
+            // and let go
+            arm_charge.throw_speed = ARM_EXTENSION_CONTROL_POWER
+                + (MAX_THROW_SPEED - ARM_EXTENSION_CONTROL_POWER) * arm_charge.fraction();
+            arm_charge.seconds = 0.0;
+        }
+    }
+
+    let extension_limit = reach.extension_limit(&bounds.arm);
+    let retraction_limit = reach.retraction_limit(&bounds.arm);
     for (mut velocity, position, mut activation) in query.iter_mut() {
-        if keyboard.pressed(EXTEND_ARM_KEY)
-            && position.position.translation.x > ARM_EXTENSION_LIMIT
-            && input_allowed.0
-        {
-            activation.wake_up(true);
-            velocity.linvel = Vec2::new(-ARM_EXTENSION_CONTROL_POWER, 0.0).into();
-        } else if keyboard.pressed(RETRACT_ARM_KEY)
-            && position.position.translation.x < ARM_RETRACTION_LIMIT
-            && input_allowed.0
-        {
-            activation.wake_up(true);
-            velocity.linvel = Vec2::new(ARM_EXTENSION_CONTROL_POWER, 0.0).into();
+        // extending moves the anchor towards the phone, which is to the left
+        let speed = if action_state.pressed(InputAction::RetractArm) && input_allowed.0 {
+            // pulling back cancels a throw
+            arm_charge.throw_speed = 0.0;
+            ARM_EXTENSION_CONTROL_POWER
+        } else if arm_charge.throw_speed > 0.0 {
+            -arm_charge.throw_speed
+        } else if !charge_throw && action_state.pressed(InputAction::ExtendArm) && input_allowed.0 {
+            -ARM_EXTENSION_CONTROL_POWER
         } else {
-            velocity.linvel = Vec2::new(0.0, 0.0).into();
+            0.0
+        };
+
+        let x = position.position.translation.x;
+        let speed = clamp_anchor_speed(
+            x,
+            speed,
+            extension_limit,
+            retraction_limit,
+            time.delta_seconds(),
+        );
+        if x <= extension_limit {
+            // thrown as far as it goes
+            arm_charge.throw_speed = 0.0;
+        }
+        if speed.abs() > 0.0 {
+            activation.wake_up(true);
         }
+        velocity.linvel = Vec2::new(speed, 0.0).into();
     }
+
+    arm_charge.throw_speed =
+        (arm_charge.throw_speed - THROW_DECELERATION * time.delta_seconds()).max(0.0);
 }
 
-/// Determines whether a finger is in the correct position to press snooze
-fn valid_press_position_system(
-    mut valid_press_position: ResMut<ValidPressPosition>,
-    snooze_button_query: Query<(&GlobalTransform, &Sprite), With<SnoozeButton>>,
-    touch_area_query: Query<&GlobalTransform, With<TouchArea>>,
-) {
-    for (snooze_transform, snooze_sprite) in snooze_button_query.iter() {
-        for touch_area_transform in touch_area_query.iter() {
-            if intersects(
-                snooze_transform,
-                snooze_sprite.custom_size,
-                touch_area_transform,
-                None,
-            ) {
-                valid_press_position.0 = true;
-                return;
+/// Rough timings for the systems that control the arm every frame, to catch them getting slower as features get added.
+/// These are slow, so they're ignored by default. Run them with `cargo test --release physics_bench -- --ignored --nocapture`.
+#[cfg(test)]
+mod physics_bench {
+    use super::*;
+    use std::time::Instant;
+
+    /// How many frames to time each case over
+    const FRAMES: u32 = 10_000;
+
+    /// How many frames to give the joints to get built before giving up
+    const MAX_SETUP_FRAMES: usize = 100;
+
+    /// How many arms to time the systems with, to see how they scale
+    const ARM_COUNTS: [usize; 3] = [1, 8, 64];
+
+    /// Sets up a headless app with the provided number of arms, with their joints built and settled and every control held down
+    fn control_app(num_arms: usize) -> App {
+        let mut action_state = ActionState::default();
+        action_state.press(InputAction::RotateHandDown);
+        action_state.press(InputAction::RotateArmDown);
+        action_state.press(InputAction::ExtendArm);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
+            .insert_resource(InputAllowed(true))
+            .insert_resource(JointSettling::default())
+            .insert_resource(action_state)
+            .insert_resource(SaveData::default())
+            .insert_resource(Reach::default())
+            .insert_resource(Bounds::default())
+            .insert_resource(ArmCharge::default())
+            .add_system(joint_settling_system);
+        for _ in 0..num_arms {
+            joint_settling_tests::spawn_arm(&mut app.world);
+        }
+
+        for _ in 0..MAX_SETUP_FRAMES {
+            app.update();
+            if app
+                .world
+                .get_resource::<JointSettling>()
+                .unwrap()
+                .joints_ready
+            {
+                break;
             }
         }
+
+        let mut joint_settling = app.world.get_resource_mut::<JointSettling>().unwrap();
+        assert!(joint_settling.joints_ready, "joints never got built");
+        joint_settling
+            .timer
+            .tick(Duration::from_secs_f32(JOINT_SETTLING_SECONDS));
+
+        app
     }
 
-    valid_press_position.0 = false;
+    #[test]
+    #[ignore]
+    fn arm_control_systems() {
+        for num_arms in ARM_COUNTS {
+            let mut app = control_app(num_arms);
+            // just the control systems, without the physics step
+            let mut stage = SystemStage::single_threaded()
+                .with_system(joint_settling_system)
+                .with_system(hand_rotation_system)
+                .with_system(arm_rotation_system)
+                .with_system(arm_extension_system);
+
+            let start = Instant::now();
+            for _ in 0..FRAMES {
+                stage.run(&mut app.world);
+            }
+            let elapsed = start.elapsed();
+
+            println!(
+                "{num_arms} arm(s): {:?} per frame over {FRAMES} frames",
+                elapsed / FRAMES
+            );
+        }
+    }
 }
 
-/// Determines whether 2 transforms intersect
-fn intersects(
-    a: &GlobalTransform,
+/// Sets up the meter showing how charged up the next arm throw is, if charged throws are enabled
+fn charge_meter_setup(mut commands: Commands, save_data: Res<SaveData>) {
+    if !save_data.settings.charge_throw {
+        return;
+    }
+    let ui_scale = save_data.settings.ui_scale();
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(
+                    Val::Px(CHARGE_METER_WIDTH * ui_scale),
+                    Val::Px(CHARGE_METER_HEIGHT * ui_scale),
+                ),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(20.0),
+                    top: Val::Percent(55.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                        ..Default::default()
+                    },
+                    color: UiColor(Color::ORANGE),
+                    ..Default::default()
+                })
+                .insert(ChargeMeter);
+        });
+}
+
+/// Handles filling up the charge meter while a throw is being charged
+fn charge_meter_system(
+    arm_charge: Res<ArmCharge>,
+    mut charge_meter_query: Query<&mut Style, With<ChargeMeter>>,
+) {
+    if !arm_charge.is_changed() {
+        return;
+    }
+
+    for mut style in charge_meter_query.iter_mut() {
+        style.size.width = Val::Percent(arm_charge.fraction() * 100.0);
+    }
+}
+
+/// The speed to move the arm anchor at to go as close to the provided speed as it can without ending up past either
+/// limit by the end of the frame, so it stops right at the limits instead of overshooting them.
+/// If it's already past one, it goes straight back to it.
+fn clamp_anchor_speed(
+    x: f32,
+    speed: f32,
+    extension_limit: f32,
+    retraction_limit: f32,
+    delta_seconds: f32,
+) -> f32 {
+    if delta_seconds <= 0.0 || !x.is_finite() {
+        // the physics recovery will sort out an anchor that's gone off to nowhere
+        return 0.0;
+    }
+
+    speed.clamp(
+        (extension_limit - x) / delta_seconds,
+        (retraction_limit - x) / delta_seconds,
+    )
+}
+
+#[cfg(test)]
+mod clamp_anchor_speed_tests {
+    use super::*;
+
+    const EXTENSION: f32 = 750.0;
+    const RETRACTION: f32 = 1700.0;
+    // exact in binary, so the positions below come out exactly
+    const DT: f32 = 0.5;
+
+    fn clamp(x: f32, speed: f32) -> f32 {
+        clamp_anchor_speed(x, speed, EXTENSION, RETRACTION, DT)
+    }
+
+    #[test]
+    fn speed_within_limits_is_unchanged() {
+        assert_eq!(-100.0, clamp(1200.0, -100.0));
+        assert_eq!(100.0, clamp(1200.0, 100.0));
+    }
+
+    #[test]
+    fn stops_right_at_extension_limit() {
+        let speed = clamp(EXTENSION + 5.0, -1000.0);
+        assert_eq!(EXTENSION, EXTENSION + 5.0 + speed * DT);
+    }
+
+    #[test]
+    fn stops_right_at_retraction_limit() {
+        let speed = clamp(RETRACTION - 5.0, 1000.0);
+        assert_eq!(RETRACTION, RETRACTION - 5.0 + speed * DT);
+    }
+
+    #[test]
+    fn cant_go_further_out_at_extension_limit() {
+        assert_eq!(0.0, clamp(EXTENSION, -100.0));
+        assert_eq!(100.0, clamp(EXTENSION, 100.0));
+    }
+
+    #[test]
+    fn cant_go_further_in_at_retraction_limit() {
+        assert_eq!(0.0, clamp(RETRACTION, 100.0));
+        assert_eq!(-100.0, clamp(RETRACTION, -100.0));
+    }
+
+    #[test]
+    fn goes_back_when_past_extension_limit() {
+        let speed = clamp(EXTENSION - 10.0, 0.0);
+        assert!(speed > 0.0);
+        assert_eq!(EXTENSION, EXTENSION - 10.0 + speed * DT);
+    }
+
+    #[test]
+    fn goes_back_when_past_retraction_limit() {
+        let speed = clamp(RETRACTION + 10.0, 0.0);
+        assert!(speed < 0.0);
+        assert_eq!(RETRACTION, RETRACTION + 10.0 + speed * DT);
+    }
+
+    #[test]
+    fn stays_still_without_a_frame_or_a_position() {
+        assert_eq!(
+            0.0,
+            clamp_anchor_speed(1200.0, 100.0, EXTENSION, RETRACTION, 0.0)
+        );
+        assert_eq!(0.0, clamp(f32::NAN, 100.0));
+        assert_eq!(0.0, clamp(f32::INFINITY, -100.0));
+    }
+}
+
+/// Handles keeping the arm anchor within its limits, in case something knocked it outside of them
+fn arm_anchor_bounds_system(
+    reach: Res<Reach>,
+    bounds: Res<Bounds>,
+    mut query: Query<
+        (
+            &mut RigidBodyPositionComponent,
+            &mut RigidBodyVelocityComponent,
+        ),
+        With<ArmAnchor>,
+    >,
+) {
+    let extension_limit = reach.extension_limit(&bounds.arm);
+    let retraction_limit = reach.retraction_limit(&bounds.arm);
+    for (mut position, mut velocity) in query.iter_mut() {
+        let x = position.position.translation.x;
+        if x >= extension_limit && x <= retraction_limit {
+            // within the limits, so the player's in control
+            continue;
+        }
+
+        let clamped_x = x.clamp(extension_limit, retraction_limit);
+        position.position.translation.x = clamped_x;
+        position.next_position.translation.x = clamped_x;
+        velocity.linvel.x = 0.0;
+    }
+}
+
+/// Determines whether a finger is in the correct position to press snooze, and which one.
+/// Only the finger the difficulty calls for counts, if it calls for one.
+fn valid_press_position_system(
+    mut valid_press_position: ResMut<ValidPressPosition>,
+    mut finger_contact: ResMut<FingerContact>,
+    snooze_button_layout: Res<SnoozeButtonLayout>,
+    difficulty: Res<Difficulty>,
+    snooze_button_query: Query<(&GlobalTransform, &Sprite, &SnoozeButton)>,
+    touch_area_query: Query<(&GlobalTransform, &Finger), With<TouchArea>>,
+    notification_query: Query<(&GlobalTransform, &Sprite), With<Notification>>,
+) {
+    let mut nearest_distance: Option<f32> = None;
+    let on_notification = |touch_area_transform: &GlobalTransform| {
+        notification_query
+            .iter()
+            .any(|(notification_transform, notification_sprite)| {
+                intersects(
+                    notification_transform,
+                    notification_sprite.custom_size,
+                    touch_area_transform,
+                    None,
+                )
+            })
+    };
+    for (snooze_transform, snooze_sprite, button) in snooze_button_query.iter() {
+        if button.0 != snooze_button_layout.active {
+            // pressing the wrong one doesn't count
+            continue;
+        }
+
+        let button_size =
+            snooze_sprite.custom_size.unwrap_or(Vec2::ONE) * snooze_transform.scale.truncate();
+        let button_min = snooze_transform.translation.truncate() - button_size / 2.0;
+        let button_max = snooze_transform.translation.truncate() + button_size / 2.0;
+
+        for (touch_area_transform, finger) in touch_area_query.iter() {
+            if difficulty
+                .required_finger()
+                .map_or(false, |required| required != *finger)
+            {
+                // wrong finger
+                continue;
+            }
+
+            let finger_position = touch_area_transform.translation.truncate();
+            let closest_point = finger_position.clamp(button_min, button_max);
+
+            if intersects(
+                snooze_transform,
+                snooze_sprite.custom_size,
+                touch_area_transform,
+                None,
+            ) && !on_notification(touch_area_transform)
+            {
+                valid_press_position.0 = Some(*finger);
+                finger_contact.contact_point = Some(closest_point);
+                finger_contact.nearest_distance = Some(0.0);
+                return;
+            }
+
+            let distance = closest_point.distance(finger_position);
+            if nearest_distance.map_or(true, |nearest| distance < nearest) {
+                nearest_distance = Some(distance);
+            }
+        }
+    }
+
+    valid_press_position.0 = None;
+    finger_contact.contact_point = None;
+    finger_contact.nearest_distance = nearest_distance;
+}
+
+/// Handles checking whether any fingers are on the dismiss button
+fn valid_dismiss_position_system(
+    mut valid_dismiss_position: ResMut<ValidDismissPosition>,
+    dismiss_button_query: Query<(&GlobalTransform, &Sprite), With<DismissButton>>,
+    touch_area_query: Query<&GlobalTransform, With<TouchArea>>,
+) {
+    valid_dismiss_position.0 =
+        dismiss_button_query
+            .iter()
+            .any(|(dismiss_transform, dismiss_sprite)| {
+                touch_area_query.iter().any(|touch_area_transform| {
+                    intersects(
+                        dismiss_transform,
+                        dismiss_sprite.custom_size,
+                        touch_area_transform,
+                        None,
+                    )
+                })
+            });
+}
+
+/// Handles ending the run on the player's own terms when they press the dismiss button
+fn dismiss_system(
+    input_allowed: Res<InputAllowed>,
+    action_state: Res<ActionState>,
+    valid_press_position: Res<ValidPressPosition>,
+    valid_dismiss_position: Res<ValidDismissPosition>,
+    death_sequence: Res<DeathSequence>,
+    mut game_over_events: EventWriter<GameOverEvent>,
+) {
+    if !input_allowed.0 || death_sequence.0.is_some() {
+        return;
+    }
+
+    // snoozing wins if a finger somehow manages to be on both buttons
+    if action_state.just_pressed(InputAction::Press)
+        && valid_dismiss_position.0
+        && valid_press_position.0.is_none()
+    {
+        // rise and shine
+        game_over_events.send(GameOverEvent {
+            reason: GameOverReason::GotUp,
+        });
+    }
+}
+
+/// Determines whether 2 transforms intersect
+fn intersects(
+    a: &GlobalTransform,
     a_sprite_custom_size: Option<Vec2>,
     b: &GlobalTransform,
     b_sprite_custom_size: Option<Vec2>,
@@ -763,311 +2886,2965 @@ fn intersects(
     let b_width = b_sprite_custom_size.unwrap_or(Vec2::ONE).x * b.scale.x;
     let b_height = b_sprite_custom_size.unwrap_or(Vec2::ONE).y * b.scale.y;
     let b_left = b.translation.x - (b_width / 2.0);
-    let b_right = b.translation.x + (b_height / 2.0);
-    let b_top = b.translation.y + (b.scale.y / 2.0);
-    let b_bottom = b.translation.y - (b.scale.y / 2.0);
+    let b_right = b.translation.x + (b_width / 2.0);
+    let b_top = b.translation.y + (b_height / 2.0);
+    let b_bottom = b.translation.y - (b_height / 2.0);
 
     a_left < b_right && a_right > b_left && a_top > b_bottom && a_bottom < b_top
 }
 
-/// Handles attempts to press the snooze button
-fn press_system(
-    mut input_allowed: ResMut<InputAllowed>,
-    mut miss_timer: ResMut<MissTimer>,
-    audio: Res<Audio>,
-    asset_server: Res<AssetServer>,
-    keyboard: Res<Input<KeyCode>>,
-    valid_press_position: Res<ValidPressPosition>,
-    mut event_writer: EventWriter<SnoozeEvent>,
+#[cfg(test)]
+mod intersects_tests {
+    use super::*;
+
+    const SIZE: Option<Vec2> = Some(Vec2::new(20.0, 10.0));
+
+    #[test]
+    fn fully_overlapping() {
+        let a = GlobalTransform::from_xyz(0.0, 0.0, 0.0);
+        let b = GlobalTransform::from_xyz(2.0, 1.0, 0.0);
+
+        assert!(intersects(&a, SIZE, &b, SIZE));
+        assert!(intersects(&b, SIZE, &a, SIZE));
+    }
+
+    #[test]
+    fn one_inside_the_other() {
+        let a = GlobalTransform::from_xyz(0.0, 0.0, 0.0);
+        let b = GlobalTransform::from_xyz(0.0, 0.0, 0.0);
+
+        assert!(intersects(&a, SIZE, &b, Some(Vec2::new(2.0, 2.0))));
+        assert!(intersects(&b, Some(Vec2::new(2.0, 2.0)), &a, SIZE));
+    }
+
+    #[test]
+    fn touching_edges_dont_intersect() {
+        let a = GlobalTransform::from_xyz(0.0, 0.0, 0.0);
+        let right = GlobalTransform::from_xyz(20.0, 0.0, 0.0);
+        let above = GlobalTransform::from_xyz(0.0, 10.0, 0.0);
+
+        assert!(!intersects(&a, SIZE, &right, SIZE));
+        assert!(!intersects(&a, SIZE, &above, SIZE));
+    }
+
+    #[test]
+    fn fully_separated() {
+        let a = GlobalTransform::from_xyz(0.0, 0.0, 0.0);
+        let b = GlobalTransform::from_xyz(100.0, -100.0, 0.0);
+
+        assert!(!intersects(&a, SIZE, &b, SIZE));
+        assert!(!intersects(&b, SIZE, &a, SIZE));
+    }
+
+    #[test]
+    fn second_height_comes_from_its_size() {
+        let a = GlobalTransform::from_xyz(0.0, 0.0, 0.0);
+        let tall = GlobalTransform::from_xyz(0.0, 40.0, 0.0);
+        let tall_size = Some(Vec2::new(20.0, 100.0));
+
+        assert!(intersects(&a, SIZE, &tall, tall_size));
+        assert!(!intersects(&a, SIZE, &tall, Some(Vec2::new(20.0, 10.0))));
+    }
+
+    #[test]
+    fn scale_applies_to_size() {
+        let a = GlobalTransform::from_xyz(0.0, 0.0, 0.0);
+        let mut b = GlobalTransform::from_xyz(30.0, 0.0, 0.0);
+
+        assert!(!intersects(&a, SIZE, &b, SIZE));
+        b.scale = Vec3::new(3.0, 1.0, 1.0);
+        assert!(intersects(&a, SIZE, &b, SIZE));
+    }
+}
+
+/// Handles popping up fake notifications on the phone while the alarm is going off, in modes that have them
+fn notification_spawn_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    alarm_active: Res<AlarmActive>,
+    font_assets: Res<FontAssets>,
+    localization: Res<Localization>,
+    mut notifications: ResMut<Notifications>,
+    mut rng: ResMut<GameRng>,
+    phone_query: Query<Entity, With<Phone>>,
+) {
+    if !notifications.enabled || !alarm_active.0 {
+        return;
+    }
+
+    if !notifications.timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    let delay = rng
+        .rng
+        .gen_range(MIN_NOTIFICATION_DELAY_SECONDS..MAX_NOTIFICATION_DELAY_SECONDS);
+    notifications.timer = Timer::from_seconds(delay, false);
+
+    let position = Vec3::new(
+        rng.rng
+            .gen_range(-NOTIFICATION_MAX_X_OFFSET..NOTIFICATION_MAX_X_OFFSET),
+        rng.rng.gen_range(NOTIFICATION_MIN_Y..NOTIFICATION_MAX_Y),
+        NOTIFICATION_Z,
+    );
+
+    for phone in phone_query.iter() {
+        let pop_in_tween = Tween::new(
+            EaseFunction::BackOut,
+            TweeningType::Once,
+            NOTIFICATION_POP_TIME,
+            TransformScaleLens {
+                start: Vec3::ZERO,
+                end: Vec3::ONE,
+            },
+        );
+        commands.entity(phone).with_children(|parent| {
+            parent
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: NOTIFICATION_COLOR,
+                        custom_size: Some(Vec2::new(NOTIFICATION_WIDTH, NOTIFICATION_HEIGHT)),
+                        ..Default::default()
+                    },
+                    transform: Transform {
+                        translation: position,
+                        scale: Vec3::ZERO,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(Notification {
+                    timer: Timer::from_seconds(NOTIFICATION_LIFETIME_SECONDS, false),
+                    dismissing: false,
+                })
+                .insert(Animator::new(pop_in_tween))
+                .with_children(|parent| {
+                    parent.spawn_bundle(Text2dBundle {
+                        text: Text::with_section(
+                            localization.localize("hud.notification"),
+                            TextStyle {
+                                font: font_assets.main.clone(),
+                                font_size: NOTIFICATION_FONT_SIZE,
+                                color: Color::WHITE,
+                            },
+                            TextAlignment {
+                                horizontal: HorizontalAlign::Center,
+                                vertical: VerticalAlign::Center,
+                            },
+                        ),
+                        transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                        ..Default::default()
+                    });
+                });
+        });
+    }
+}
+
+/// Handles getting rid of notifications once they've been up for long enough
+fn notification_dismiss_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut notification_query: Query<(Entity, &Transform, &mut Notification)>,
+) {
+    for (entity, transform, mut notification) in notification_query.iter_mut() {
+        if !notification.timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        if notification.dismissing {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let pop_out_tween = Tween::new(
+            EaseFunction::QuadraticIn,
+            TweeningType::Once,
+            NOTIFICATION_POP_TIME,
+            TransformScaleLens {
+                start: transform.scale,
+                end: Vec3::ZERO,
+            },
+        );
+        commands.entity(entity).insert(Animator::new(pop_out_tween));
+        notification.timer = Timer::new(NOTIFICATION_POP_TIME, false);
+        notification.dismissing = true;
+    }
+}
+
+/// Handles attempts to press the snooze button
+fn press_system(
+    input_allowed: Res<InputAllowed>,
+    mut snooze_streak: ResMut<SnoozeStreak>,
+    mut press_hold_timer: ResMut<PressHoldTimer>,
+    save_data: Res<SaveData>,
+    difficulty: Res<Difficulty>,
+    time: Res<Time>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    action_state: Res<ActionState>,
+    valid_press_position: Res<ValidPressPosition>,
+    valid_dismiss_position: Res<ValidDismissPosition>,
+    mut duck_timer: ResMut<DuckTimer>,
+    mut event_writer: EventWriter<SnoozeEvent>,
+    mut miss_events: EventWriter<MissEvent>,
+    mut log_events: EventWriter<GameLogEvent>,
+) {
+    if !input_allowed.0 {
+        press_hold_timer.0 = None;
+        return;
+    }
+
+    if action_state.just_pressed(InputAction::Press) {
+        // hits that count get higher and higher the longer the streak goes on
+        let pitch = match valid_press_position.0 {
+            Some(finger) if keeps_streak(finger, difficulty.required_finger()) => {
+                combo_pitch(snooze_streak.0)
+            }
+            Some(_) => combo_pitch(0),
+            None => 1.0,
+        };
+        play_pitched_sfx(
+            &audio,
+            asset_server.load(HIT_SOUND),
+            COMBO_SFX_CHANNEL,
+            pitch,
+            &save_data.settings,
+        );
+        duck_timer.duck();
+        if let Some(finger) = valid_press_position.0 {
+            if save_data.settings.hold_to_press {
+                // gotta hold it down for a bit first
+                press_hold_timer.0 =
+                    Some(Timer::from_seconds(difficulty.press_hold_seconds(), false));
+            } else {
+                // gotcha
+                press_with_finger(finger, difficulty.required_finger(), &mut snooze_streak);
+                log_events.send(GameLogEvent::SnoozePressed(finger));
+                event_writer.send(SnoozeEvent);
+            }
+        } else if valid_dismiss_position.0 {
+            // getting up is handled by the dismiss system
+        } else {
+            // and that's a bad miss
+            log_events.send(GameLogEvent::Missed);
+            snooze_streak.0 = 0;
+            miss_events.send(MissEvent);
+        }
+        return;
+    }
+
+    if press_hold_timer.0.is_none() {
+        return;
+    }
+
+    let finger = match valid_press_position.0 {
+        Some(finger) if action_state.pressed(InputAction::Press) => finger,
+        _ => {
+            // let go too early
+            press_hold_timer.0 = None;
+            return;
+        }
+    };
+
+    if press_hold_timer
+        .0
+        .as_mut()
+        .map_or(false, |timer| timer.tick(time.delta()).just_finished())
+    {
+        // gotcha
+        press_with_finger(finger, difficulty.required_finger(), &mut snooze_streak);
+        log_events.send(GameLogEvent::SnoozePressed(finger));
+        press_hold_timer.0 = None;
+        event_writer.send(SnoozeEvent);
+    }
+}
+
+/// Whether pressing the snooze button with the provided finger keeps the streak going. Any finger does, unless the
+/// difficulty requires a specific one.
+fn keeps_streak(finger: Finger, required_finger: Option<Finger>) -> bool {
+    required_finger.map_or(true, |required| required == finger)
+}
+
+/// Accounts for which finger got the snooze button. If the difficulty requires a specific finger, any other one starts
+/// the streak over, and the snooze that's about to happen only gets the base reward.
+fn press_with_finger(
+    finger: Finger,
+    required_finger: Option<Finger>,
+    snooze_streak: &mut SnoozeStreak,
+) {
+    if !keeps_streak(finger, required_finger) {
+        snooze_streak.0 = 0;
+    }
+}
+
+/// Handles counting misses and disabling input for a bit after each one, then re-enabling it once the penalty time has elapsed
+fn miss_penalty_system(
+    mut input_allowed: ResMut<InputAllowed>,
+    mut miss_timer: ResMut<MissTimer>,
+    mut miss_count: ResMut<MissCount>,
+    miss_penalty: Res<MissPenalty>,
+    time: Res<Time>,
+    mut miss_events: EventReader<MissEvent>,
+) {
+    if miss_timer.0.tick(time.delta()).just_finished() {
+        input_allowed.0 = true;
+    }
+
+    for _ in miss_events.iter() {
+        miss_count.0 += 1;
+        if let Some(penalty_seconds) = miss_penalty.0 {
+            input_allowed.0 = false;
+            miss_timer.0 = Timer::from_seconds(penalty_seconds, false);
+        }
+    }
+}
+
+/// Handles showing that the player missed, fading the message out over the miss penalty and taking it down right as
+/// input comes back
+fn miss_feedback_system(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+    miss_penalty: Res<MissPenalty>,
+    miss_timer: Res<MissTimer>,
+    mut miss_events: EventReader<MissEvent>,
+    mut indicator_query: Query<(Entity, &mut Text), With<MissIndicator>>,
+) {
+    if miss_events.iter().count() > 0 && miss_penalty.0.is_some() {
+        for (entity, _) in indicator_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        spawn_miss_indicator(&mut commands, &font_assets, &save_data, &localization);
+        return;
+    }
+
+    for (entity, mut text) in indicator_query.iter_mut() {
+        if miss_timer.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            text.sections[0]
+                .style
+                .color
+                .set_a(1.0 - miss_timer.0.percent());
+        }
+    }
+}
+
+/// Spawns the message letting the player know they missed
+fn spawn_miss_indicator(
+    commands: &mut Commands,
+    font_assets: &FontAssets,
+    save_data: &SaveData,
+    localization: &Localization,
+) {
+    let ui_scale = save_data.settings.ui_scale();
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                localization.localize("hud.missed"),
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 40.0 * ui_scale,
+                    color: MISS_INDICATOR_COLOR,
+                },
+                TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(15.0 + 60.0 * ui_scale),
+                    right: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(MissIndicator);
+}
+
+/// Handles when the snooze button is pressed
+fn snooze_system(
+    mut time: ResMut<GameTime>,
+    mut num_snoozes: ResMut<NumSnoozes>,
+    mut input_allowed: ResMut<InputAllowed>,
+    mut alarm_active: ResMut<AlarmActive>,
+    mut vibrate_timer: ResMut<VibrateTimer>,
+    mut vibration_intensity: ResMut<VibrationIntensity>,
+    wake_goal: Res<WakeGoal>,
+    game_mode: Res<GameMode>,
+    snooze_reward: Res<SnoozeReward>,
+    mut snooze_streak: ResMut<SnoozeStreak>,
+    mut save_data: ResMut<SaveData>,
+    difficulty: Res<Difficulty>,
+    audio: Res<Audio>,
+    audio_assets: Res<AudioAssets>,
+    mut event_reader: EventReader<SnoozeEvent>,
+    (mut fade_events, mut log_events): (EventWriter<FadeEvent>, EventWriter<GameLogEvent>),
+) {
+    // read all of them, so any extras sent in the same frame don't carry over into the next one
+    if event_reader.iter().count() == 0 {
+        // no snoozin
+        return;
+    }
+
+    if !alarm_active.0 {
+        // already snoozed this alarm
+        return;
+    }
+
+    // disallow input
+    input_allowed.0 = false;
+
+    // turn off the alarm
+    alarm_active.0 = false;
+
+    // ding
+    play_sfx(&audio, audio_assets.snooze.clone(), &save_data.settings);
+
+    // increment snooze counter
+    num_snoozes.0 += 1;
+    save_data.stats.total_snoozes += 1;
+
+    // update time
+    time.advance(snooze_reward.minutes(snooze_streak.0));
+    log_events.send(GameLogEvent::TimeAdvanced(*time));
+    snooze_streak.0 += 1;
+
+    if vibrate_timer.0.duration().as_secs_f32() > VIBRATE_TIME.as_secs_f32() {
+        // a little bit faster now
+        vibrate_timer.0 = Timer::from_seconds(
+            vibrate_timer.0.duration().as_secs_f32() * difficulty.vibration_speedup(),
+            true,
+        );
+    }
+
+    if *game_mode == GameMode::NightOwl && time.minutes_past(&wake_goal.0) >= 0 {
+        // should've gotten up by now, so things start getting violent
+        vibration_intensity.0 =
+            (vibration_intensity.0 + NIGHT_OWL_INTENSITY_INCREASE).min(NIGHT_OWL_MAX_INTENSITY);
+    }
+
+    // fade out
+    fade_events.send(FadeEvent(FadeDirection::Out));
+}
+
+#[cfg(test)]
+mod snooze_tests {
+    use super::*;
+
+    pub(super) fn snooze_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(STARTING_TIME);
+        world.insert_resource(NumSnoozes(0));
+        world.insert_resource(InputAllowed(true));
+        world.insert_resource(AlarmActive(true));
+        world.insert_resource(VibrateTimer(Timer::from_seconds(1.5, true)));
+        world.insert_resource(VibrationIntensity(1.0));
+        world.insert_resource(WakeGoal(WAKE_GOAL));
+        world.insert_resource(GameMode::Classic);
+        world.insert_resource(SnoozeReward::default());
+        world.insert_resource(SnoozeStreak(0));
+        world.insert_resource(SaveData::default());
+        world.insert_resource(Difficulty::default());
+        world.insert_resource(Audio::default());
+        world.insert_resource(AudioAssets {
+            alarm: Handle::default(),
+            louder_alarm: Handle::default(),
+            frantic_alarm: Handle::default(),
+            hit: Handle::default(),
+            drop: Handle::default(),
+            snooze: Handle::default(),
+            game_over_win: Handle::default(),
+            game_over_lose: Handle::default(),
+        });
+        world.insert_resource(Events::<SnoozeEvent>::default());
+        world.insert_resource(Events::<FadeEvent>::default());
+        world.insert_resource(Events::<GameLogEvent>::default());
+
+        world
+    }
+
+    pub(super) fn send_snoozes(world: &mut World, count: usize) {
+        let mut snooze_events = world.get_resource_mut::<Events<SnoozeEvent>>().unwrap();
+        for _ in 0..count {
+            snooze_events.send(SnoozeEvent);
+        }
+    }
+
+    pub(super) fn minutes_since_midnight(world: &World) -> i32 {
+        world
+            .get_resource::<GameTime>()
+            .unwrap()
+            .minutes_since_midnight()
+    }
+
+    #[test]
+    fn snoozing_pushes_the_clock_back_and_quiets_the_alarm() {
+        let mut world = snooze_world();
+        let mut stage = SystemStage::single(snooze_system);
+
+        send_snoozes(&mut world, 1);
+        stage.run(&mut world);
+
+        assert_eq!(1, world.get_resource::<NumSnoozes>().unwrap().0);
+        assert_eq!(
+            STARTING_TIME.minutes_since_midnight() + i32::from(SNOOZE_MINUTES),
+            minutes_since_midnight(&world)
+        );
+        assert!(!world.get_resource::<AlarmActive>().unwrap().0);
+        assert!(!world.get_resource::<InputAllowed>().unwrap().0);
+    }
+
+    #[test]
+    fn two_snoozes_in_one_frame_only_count_once() {
+        let mut world = snooze_world();
+        let mut stage = SystemStage::single(snooze_system);
+        let reward = SnoozeReward::default().minutes(0);
+
+        send_snoozes(&mut world, 2);
+        stage.run(&mut world);
+
+        assert_eq!(
+            STARTING_TIME.minutes_since_midnight() + i32::from(reward),
+            minutes_since_midnight(&world)
+        );
+        assert_eq!(1, world.get_resource::<NumSnoozes>().unwrap().0);
+        assert_eq!(1, world.get_resource::<SnoozeStreak>().unwrap().0);
+        assert!(!world.get_resource::<AlarmActive>().unwrap().0);
+    }
+
+    #[test]
+    fn extra_snoozes_dont_carry_over_to_the_next_alarm() {
+        let mut world = snooze_world();
+        let mut stage = SystemStage::single(snooze_system);
+
+        send_snoozes(&mut world, 2);
+        stage.run(&mut world);
+        let after_first_snooze = minutes_since_midnight(&world);
+
+        // the next alarm starts without anything new being pressed
+        world.get_resource_mut::<AlarmActive>().unwrap().0 = true;
+        stage.run(&mut world);
+
+        assert_eq!(after_first_snooze, minutes_since_midnight(&world));
+        assert_eq!(1, world.get_resource::<NumSnoozes>().unwrap().0);
+    }
+
+    #[test]
+    fn snoozing_a_snoozed_alarm_does_nothing() {
+        let mut world = snooze_world();
+        world.get_resource_mut::<AlarmActive>().unwrap().0 = false;
+        let mut stage = SystemStage::single(snooze_system);
+
+        send_snoozes(&mut world, 1);
+        stage.run(&mut world);
+
+        assert_eq!(
+            STARTING_TIME.minutes_since_midnight(),
+            minutes_since_midnight(&world)
+        );
+        assert_eq!(0, world.get_resource::<NumSnoozes>().unwrap().0);
+    }
+}
+
+/// Handles writing what happens during a run to the console, for debugging
+fn log_events_system(mut log_events: EventReader<GameLogEvent>) {
+    for event in log_events.iter() {
+        match event {
+            GameLogEvent::SnoozePressed(finger) => {
+                info!("you pressed snooze with your {finger:?} finger")
+            }
+            GameLogEvent::Missed => info!("you missed"),
+            GameLogEvent::KnockedSomethingOver => info!("you knocked something over"),
+            GameLogEvent::TimeAdvanced(time) => info!("advanced time to {time}"),
+        }
+    }
+}
+
+/// Handles updates while the player gets a few minutes of precious sleep
+fn sleep_system(
+    mut event_reader: EventReader<TweenCompleted>,
+    time: Res<GameTime>,
+    num_snoozes: Res<NumSnoozes>,
+    starting_pose: Res<StartingPose>,
+    rest_positions: Res<RestPositions>,
+    mut sleep_timer: ResMut<SleepTimer>,
+    mut time_display_query: Query<&mut Text, With<TimeDisplay>>,
+    mut arm_part_query: ArmPartQuery,
+) {
+    for event in event_reader.iter() {
+        if event.user_data != FADE_OUT_TWEEN_COMPLETED {
+            continue;
+        }
+
+        // update the time display
+        for mut time_text in time_display_query.iter_mut() {
+            time_text.sections[0].value = time.to_string();
+        }
+
+        // put the arm back how it started while the screen is dark, so each morning starts fresh
+        reset_arm_pose(&starting_pose, &rest_positions, &mut arm_part_query);
+
+        // zzz
+        sleep_timer.start(num_snoozes.0);
+    }
+}
+
+/// Handles waking the player back up once they've slept for a bit. Input stays disabled until then.
+fn wake_system(
+    time: Res<Time>,
+    mut sleep_timer: ResMut<SleepTimer>,
+    mut input_allowed: ResMut<InputAllowed>,
+    mut alarm_active: ResMut<AlarmActive>,
+    mut event_writer: EventWriter<FadeEvent>,
+) {
+    let finished = sleep_timer
+        .0
+        .as_mut()
+        .map_or(false, |timer| timer.tick(time.delta()).finished());
+    if !finished {
+        return;
+    }
+
+    sleep_timer.0 = None;
+
+    // allow input
+    input_allowed.0 = true;
+
+    // turn the alarm back on, which restarts its sound
+    alarm_active.0 = true;
+
+    // fade in
+    event_writer.send(FadeEvent(FadeDirection::In))
+}
+
+/// Handles vibrating the phone around
+fn vibration_system(
+    mut commands: Commands,
+    alarm_active: Res<AlarmActive>,
+    time: Res<Time>,
+    mut vibrate_timer: ResMut<VibrateTimer>,
+    vibration_intensity: Res<VibrationIntensity>,
+    vibration_pattern: Res<VibrationPattern>,
+    tween_easing: Res<TweenEasing>,
+    tension: Res<Tension>,
+    idle: Res<Idle>,
+    difficulty: Res<Difficulty>,
+    bounds: Res<Bounds>,
+    gravity: Res<Gravity>,
+    num_snoozes: Res<NumSnoozes>,
+    mut rng: ResMut<GameRng>,
+    phone_query: Query<(Entity, &Transform), With<Phone>>,
+) {
+    if !alarm_active.0 {
+        return;
+    }
+
+    if vibrate_timer.0.tick(time.delta()).finished() {
+        for (entity, transform) in phone_query.iter() {
+            vibrate_phone(
+                &mut commands,
+                entity,
+                transform,
+                difficulty.max_vibrate_translation(),
+                vibration_intensity.0
+                    * snooze_vibration_intensity(num_snoozes.0)
+                    * (1.0 + tension.0 * TENSION_EXTRA_VIBRATION)
+                    * (1.0 + idle.escalation() * IDLE_EXTRA_VIBRATION),
+                *vibration_pattern,
+                tween_easing.vibration,
+                &bounds.table,
+                gravity.0,
+                &mut rng.rng,
+            );
+        }
+    }
+}
+
+/// How violent the vibrations are after the provided number of snoozes, as a multiplier. Goes up a bit with each snooze
+/// until it hits the cap.
+fn snooze_vibration_intensity(num_snoozes: u32) -> f32 {
+    (1.0 + num_snoozes as f32 * SNOOZE_INTENSITY_STEP).min(MAX_SNOOZE_INTENSITY)
+}
+
+#[cfg(test)]
+mod snooze_vibration_intensity_tests {
+    use super::*;
+
+    #[test]
+    fn starts_at_normal_intensity() {
+        assert_eq!(1.0, snooze_vibration_intensity(0));
+    }
+
+    #[test]
+    fn never_decreases() {
+        let mut last = snooze_vibration_intensity(0);
+        for num_snoozes in 1..1000 {
+            let intensity = snooze_vibration_intensity(num_snoozes);
+            assert!(
+                intensity >= last,
+                "intensity went down from {last} to {intensity} at {num_snoozes} snoozes"
+            );
+            last = intensity;
+        }
+    }
+
+    #[test]
+    fn goes_up_with_early_snoozes() {
+        assert!(snooze_vibration_intensity(1) > snooze_vibration_intensity(0));
+        assert!(snooze_vibration_intensity(5) > snooze_vibration_intensity(4));
+    }
+
+    #[test]
+    fn never_goes_over_the_cap() {
+        for num_snoozes in (0..1000).chain([u32::MAX]) {
+            assert!(snooze_vibration_intensity(num_snoozes) <= MAX_SNOOZE_INTENSITY);
+        }
+        assert_eq!(MAX_SNOOZE_INTENSITY, snooze_vibration_intensity(u32::MAX));
+    }
+}
+
+/// Vibrates the phone to a random position, picked according to the provided pattern and kept on the table
+fn vibrate_phone(
+    commands: &mut Commands,
+    entity: Entity,
+    start: &Transform,
+    max_translation: f32,
+    intensity: f32,
+    pattern: VibrationPattern,
+    easing: EaseFunction,
+    table_bounds: &TableBounds,
+    gravity: Vec2,
+    rng: &mut StdRng,
+) {
+    let start_position = start.translation;
+    let start_rotation = start.rotation;
+    let max_translation = max_translation * intensity;
+    let max_rotation = MAX_VIBRATE_ROTATION * intensity;
+
+    let end_position = table_bounds.clamp_inside(
+        pattern.target(start_position, max_translation, rng),
+        start_position,
+        VIBRATE_EDGE_INSET,
+    ) + (gravity * VIBRATE_TIME.as_secs_f32()).extend(0.0);
+    let position_tween = Tween::new(
+        easing,
+        TweeningType::Once,
+        VIBRATE_TIME,
+        TransformPositionLens {
+            start: start_position,
+            end: end_position,
+        },
+    )
+    .with_completed_event(true, VIBRATE_TWEEN_COMPLETED);
+
+    let end_rotation =
+        rng.gen_range((start_rotation.z - max_rotation)..(start_rotation.z + max_rotation));
+    let rotation_tween = Tween::new(
+        easing,
+        TweeningType::Once,
+        VIBRATE_TIME,
+        TransformRotationLens {
+            start: start_rotation,
+            end: Quat::from_rotation_z(end_rotation),
+        },
+    )
+    .with_completed_event(true, VIBRATE_TWEEN_COMPLETED);
+
+    commands
+        .entity(entity)
+        .insert(Animator::new(Tracks::new(vec![
+            position_tween,
+            rotation_tween,
+        ])));
+}
+
+/// Handles checking to make sure the phone is still on the table
+fn table_bounds_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    phone_query: Query<(Entity, &Transform), With<Phone>>,
+    camera_query: Query<(Entity, &Transform), With<MainCamera>>,
+    bounds: Res<Bounds>,
+    death_sequence: Res<DeathSequence>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+    mut integration_parameters: ResMut<IntegrationParameters>,
+    mut game_over_events: EventWriter<GameOverEvent>,
+) {
+    if death_sequence.0.is_some() {
+        // already falling
+        return;
+    }
+
+    for (entity, transform) in phone_query.iter() {
+        if !bounds.table.contains(transform.translation) {
+            // it fell off
+            game_over_events.send(GameOverEvent {
+                reason: GameOverReason::PhoneFell,
+            });
+
+            // slow everything down for dramatic effect
+            rapier_config.timestep_mode = TimestepMode::FixedTimestep;
+            integration_parameters.dt = slow_motion_timestep(&time);
+
+            tumble_phone(&mut commands, entity, transform);
+            for (camera_entity, camera_transform) in camera_query.iter() {
+                zoom_camera(
+                    &mut commands,
+                    camera_entity,
+                    camera_transform,
+                    transform.translation,
+                );
+            }
+
+            return;
+        }
+    }
+}
+
+/// Handles the run being lost, stopping everything so the death sequence can play out
+fn game_over_system(
+    mut input_allowed: ResMut<InputAllowed>,
+    mut alarm_active: ResMut<AlarmActive>,
+    mut death_sequence: ResMut<DeathSequence>,
+    mut game_over_reason: ResMut<GameOverReason>,
+    mut game_over_events: EventReader<GameOverEvent>,
+) {
+    // if more than one thing lost the run at once, the first one gets the credit
+    let reason = match game_over_events.iter().next() {
+        Some(event) => event.reason,
+        None => return,
+    };
+
+    if death_sequence.0.is_some() {
+        // already lost
+        return;
+    }
+
+    *game_over_reason = reason;
+    input_allowed.0 = false;
+    alarm_active.0 = false;
+    let length = if reason == GameOverReason::PhoneFell {
+        // long enough for the slowed down tumble to finish
+        slowed(TUMBLE_TIME)
+    } else {
+        DEATH_SEQUENCE_TIME
+    };
+    death_sequence.0 = Some(Timer::new(length, false));
+}
+
+/// How long something that takes the provided amount of time normally takes in slow motion
+fn slowed(duration: Duration) -> Duration {
+    duration * SLOW_MOTION_FACTOR
+}
+
+/// How far to step the physics this frame to keep it going at the slow motion speed
+fn slow_motion_timestep(time: &Time) -> f32 {
+    time.delta_seconds() / SLOW_MOTION_FACTOR as f32
+}
+
+/// Keeps the physics going at a fixed fraction of real time while the phone is falling, however fast frames are
+/// coming in. The tumble and camera zoom are slowed down by the same amount, so everything stays in step.
+fn slow_motion_system(
+    time: Res<Time>,
+    rapier_config: Res<RapierConfiguration>,
+    mut integration_parameters: ResMut<IntegrationParameters>,
+) {
+    if matches!(rapier_config.timestep_mode, TimestepMode::FixedTimestep) {
+        integration_parameters.dt = slow_motion_timestep(&time);
+    }
+}
+
+/// Handles the sounds for losing the run, and for the run being over once the death sequence is done
+fn game_over_sound_system(
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    audio_assets: Res<AudioAssets>,
+    save_data: Res<SaveData>,
+    alarm_channel: Res<AlarmChannel>,
+    mut duck_timer: ResMut<DuckTimer>,
+    mut game_over_events: EventReader<GameOverEvent>,
+    mut game_result_events: EventReader<GameResultEvent>,
+) {
+    if let Some(event) = game_over_events.iter().next() {
+        if event.reason == GameOverReason::PhoneFell {
+            play_sfx(&audio, asset_server.load(DROP_SOUND), &save_data.settings);
+            duck_timer.duck();
+        }
+    }
+
+    if let Some(GameResultEvent(result)) = game_result_events.iter().next() {
+        // make sure the alarm is done so the stinger can be heard on its own
+        audio.stop_channel(&alarm_channel.0);
+        let stinger = if result.won {
+            audio_assets.game_over_win.clone()
+        } else {
+            audio_assets.game_over_lose.clone()
+        };
+        play_sfx(&audio, stinger, &save_data.settings);
+    }
+}
+
+/// Sends the phone tumbling off the edge of the table
+fn tumble_phone(commands: &mut Commands, entity: Entity, transform: &Transform) {
+    let start_position = transform.translation;
+    let direction = start_position.truncate().normalize_or_zero();
+    let end_position = start_position + (direction * TUMBLE_DISTANCE).extend(0.0);
+
+    let position_tween = Tween::new(
+        EaseFunction::QuadraticIn,
+        TweeningType::Once,
+        slowed(TUMBLE_TIME),
+        TransformPositionLens {
+            start: start_position,
+            end: end_position,
+        },
+    );
+
+    let rotation_tween = Tween::new(
+        EaseFunction::QuadraticIn,
+        TweeningType::Once,
+        slowed(TUMBLE_TIME),
+        TransformRotationLens {
+            start: transform.rotation,
+            end: transform.rotation * Quat::from_rotation_z(TUMBLE_ROTATION),
+        },
+    );
+
+    let scale_tween = Tween::new(
+        EaseFunction::QuadraticIn,
+        TweeningType::Once,
+        slowed(TUMBLE_TIME),
+        TransformScaleLens {
+            start: transform.scale,
+            end: transform.scale * TUMBLE_SCALE_FACTOR,
+        },
+    );
+
+    commands
+        .entity(entity)
+        .insert(Animator::new(Tracks::new(vec![
+            position_tween,
+            rotation_tween,
+            scale_tween,
+        ])));
+}
+
+/// Zooms the camera in toward the provided position
+fn zoom_camera(commands: &mut Commands, entity: Entity, transform: &Transform, target: Vec3) {
+    let position_tween = Tween::new(
+        EaseFunction::SineInOut,
+        TweeningType::Once,
+        slowed(TUMBLE_TIME),
+        TransformPositionLens {
+            start: transform.translation,
+            end: Vec3::new(target.x, target.y, transform.translation.z),
+        },
+    );
+
+    let scale_tween = Tween::new(
+        EaseFunction::SineInOut,
+        TweeningType::Once,
+        slowed(TUMBLE_TIME),
+        TransformScaleLens {
+            start: transform.scale,
+            end: Vec3::new(DEATH_ZOOM_SCALE, DEATH_ZOOM_SCALE, transform.scale.z),
+        },
+    );
+
+    commands
+        .entity(entity)
+        .insert(Animator::new(Tracks::new(vec![position_tween, scale_tween])));
+}
+
+/// Handles wrapping up the run once the phone has finished tumbling, so the game over screen can show how it went
+fn death_sequence_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_time: Res<GameTime>,
+    num_snoozes: Res<NumSnoozes>,
+    mut death_sequence: ResMut<DeathSequence>,
+    mut save_data: ResMut<SaveData>,
+    difficulty: Res<Difficulty>,
+    game_mode: Res<GameMode>,
+    wake_goal: Res<WakeGoal>,
+    rng: Res<GameRng>,
+    game_over_reason: Res<GameOverReason>,
+    run_play_time: Res<RunPlayTime>,
+    mut last_game_result: ResMut<LastGameResult>,
+    phone_query: Query<Entity, With<Phone>>,
+    mut event_writer: EventWriter<GameResultEvent>,
+) {
+    let finished = match death_sequence.0.as_mut() {
+        Some(timer) => timer.tick(time.delta()).just_finished(),
+        None => false,
+    };
+
+    if !finished {
+        return;
+    }
+
+    for entity in phone_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let result = GameResult {
+        final_time: *game_time,
+        snoozes: num_snoozes.0,
+        mode: *game_mode,
+        difficulty: *difficulty,
+        reason: *game_over_reason,
+        won: game_over_reason.is_win(),
+        real_seconds: run_play_time.0 as f32,
+        wake_goal: wake_goal.0,
+        seed: rng.seed,
+    };
+    save_data.record_result(&result);
+    last_game_result.0 = Some(result);
+    event_writer.send(GameResultEvent(result));
+}
+
+/// Handles showing how the run went once it's over, or getting ready to go again if the player wants to skip that
+fn game_over_screen_system(
+    mut commands: Commands,
+    save_data: Res<SaveData>,
+    theme: Res<Theme>,
+    localization: Res<Localization>,
+    font_assets: Res<FontAssets>,
+    asset_server: Res<AssetServer>,
+    mut auto_retry_timer: ResMut<AutoRetryTimer>,
+    mut event_reader: EventReader<GameResultEvent>,
+) {
+    let result = match event_reader.iter().next() {
+        Some(event) => event.0,
+        None => return,
+    };
+
+    if !save_data.settings.auto_retry {
+        show_game_over_screen(
+            &mut commands,
+            result,
+            save_data.best_score_for(&result),
+            game_over_panel_color(result.reason, &theme),
+            &localization,
+            save_data.settings.ui_scale(),
+            asset_server,
+        );
+        return;
+    }
+
+    auto_retry_timer.0 = Some(Timer::from_seconds(AUTO_RETRY_DELAY_SECONDS, false));
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                localization.localize("hud.restarting"),
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 20.0 * save_data.settings.ui_scale(),
+                    color: Color::GRAY,
+                },
+                TextAlignment::default(),
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    bottom: Val::Px(15.0),
+                    right: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(GameComponent);
+}
+
+/// Handles starting a new run once the auto retry delay is up, or backing out to the menu instead
+fn auto_retry_system(
+    time: Res<Time>,
+    keyboard: Res<Input<KeyCode>>,
+    mut game_state: ResMut<State<GameState>>,
+    mut auto_retry_timer: ResMut<AutoRetryTimer>,
+) {
+    let timer = match auto_retry_timer.0.as_mut() {
+        Some(timer) => timer,
+        None => return,
+    };
+
+    if keyboard.just_pressed(AUTO_RETRY_CANCEL_KEY) {
+        auto_retry_timer.0 = None;
+        game_state.set(GameState::Menu).unwrap();
+    } else if timer.tick(time.delta()).just_finished() {
+        auto_retry_timer.0 = None;
+        game_state.set(GameState::GameLoading).unwrap();
+    }
+}
+
+/// Puts everything that changes over the course of a run back to how it starts out
+fn reset_game_state(
+    mut time: ResMut<GameTime>,
+    mut num_snoozes: ResMut<NumSnoozes>,
+    mut input_allowed: ResMut<InputAllowed>,
+    mut alarm_active: ResMut<AlarmActive>,
+    mut vibrate_timer: ResMut<VibrateTimer>,
+    mut miss_timer: ResMut<MissTimer>,
+    mut vibration_intensity: ResMut<VibrationIntensity>,
+    mut tension: ResMut<Tension>,
+    mut press_hold_timer: ResMut<PressHoldTimer>,
+    mut sleep_timer: ResMut<SleepTimer>,
+    mut camera_shake: ResMut<CameraShake>,
+    mut phone_trail: ResMut<PhoneTrail>,
+    mut arm_charge: ResMut<ArmCharge>,
+    mut joint_settling: ResMut<JointSettling>,
+    difficulty: Res<Difficulty>,
+) {
+    *time = STARTING_TIME;
+    num_snoozes.0 = 0;
+    input_allowed.0 = true;
+    alarm_active.0 = true;
+    vibrate_timer.0 = Timer::from_seconds(difficulty.vibration_delay_seconds(), true);
+    miss_timer.0 = Timer::from_seconds(MISS_PENALTY_SECONDS, false);
+    vibration_intensity.0 = 1.0;
+    tension.0 = 0.0;
+    press_hold_timer.0 = None;
+    sleep_timer.0 = None;
+    camera_shake.0 = 0.0;
+    phone_trail.last_position = None;
+    *arm_charge = ArmCharge::default();
+    *joint_settling = JointSettling::default();
+}
+
+/// Throws away any events left over from the run, so they don't set anything off in the next one.
+/// Everything with an animator is either tagged `GameComponent` or has its animator removed on exit,
+/// so nothing new will come in after this.
+fn clear_game_events(
+    mut tween_events: ResMut<Events<TweenCompleted>>,
+    mut fade_events: ResMut<Events<FadeEvent>>,
+    mut snooze_events: ResMut<Events<SnoozeEvent>>,
+    mut miss_events: ResMut<Events<MissEvent>>,
+    mut log_events: ResMut<Events<GameLogEvent>>,
+    mut game_over_events: ResMut<Events<GameOverEvent>>,
+    mut game_result_events: ResMut<Events<GameResultEvent>>,
+) {
+    tween_events.clear();
+    fade_events.clear();
+    snooze_events.clear();
+    miss_events.clear();
+    log_events.clear();
+    game_over_events.clear();
+    game_result_events.clear();
+}
+
+#[cfg(test)]
+mod clear_game_events_tests {
+    use super::snooze_tests::{minutes_since_midnight, send_snoozes, snooze_world};
+    use super::*;
+
+    /// A world partway through a run that was just left, with a snooze and a finished fade out still queued up
+    fn left_run_world() -> World {
+        let mut world = snooze_world();
+        world.insert_resource(StartingPose {
+            arm_anchor_x: 1000.0,
+            arm_anchor_y: -100.0,
+            arm_angle: 0.5,
+            hand_angle: -0.5,
+        });
+        world.insert_resource(RestPositions {
+            arm: Vec3::new(500.0, -100.0, 0.0),
+            hand: Vec3::new(100.0, -100.0, 0.0),
+        });
+        world.insert_resource(SleepTimer(None));
+        world.insert_resource(Events::<TweenCompleted>::default());
+        world.insert_resource(Events::<MissEvent>::default());
+        world.insert_resource(Events::<GameOverEvent>::default());
+        world.insert_resource(Events::<GameResultEvent>::default());
+
+        send_snoozes(&mut world, 1);
+        let overlay = world.spawn().id();
+        world
+            .get_resource_mut::<Events<TweenCompleted>>()
+            .unwrap()
+            .send(TweenCompleted {
+                entity: overlay,
+                user_data: FADE_OUT_TWEEN_COMPLETED,
+            });
+
+        world
+    }
+
+    /// Runs the systems that react to snoozes and fades, like they would on the first frame of a new run
+    fn reenter(world: &mut World) {
+        SystemStage::single(snooze_system).run(world);
+        SystemStage::single(sleep_system).run(world);
+    }
+
+    #[test]
+    fn leftover_events_would_fire_without_clearing() {
+        let mut world = left_run_world();
+
+        reenter(&mut world);
+
+        assert!(world.get_resource::<SleepTimer>().unwrap().0.is_some());
+        assert_ne!(
+            STARTING_TIME.minutes_since_midnight(),
+            minutes_since_midnight(&world)
+        );
+    }
+
+    #[test]
+    fn leftover_events_dont_fire_after_reentering() {
+        let mut world = left_run_world();
+
+        SystemStage::single(clear_game_events).run(&mut world);
+        reenter(&mut world);
+
+        assert!(world.get_resource::<SleepTimer>().unwrap().0.is_none());
+        assert_eq!(
+            STARTING_TIME.minutes_since_midnight(),
+            minutes_since_midnight(&world)
+        );
+        assert_eq!(0, world.get_resource::<NumSnoozes>().unwrap().0);
+        assert!(world.get_resource::<AlarmActive>().unwrap().0);
+    }
+}
+
+/// The tint behind the game over text for a run that ended for the provided reason
+fn game_over_panel_color(reason: GameOverReason, theme: &Theme) -> Color {
+    match reason {
+        GameOverReason::GotUp | GameOverReason::FinallyUp => WIN_PANEL_COLOR,
+        GameOverReason::Overslept | GameOverReason::LateForWork => LATE_PANEL_COLOR,
+        GameOverReason::PhoneFell
+        | GameOverReason::TooManyMisses
+        | GameOverReason::Panicked
+        | GameOverReason::FellAsleep => theme.game_over_panel_color,
+    }
+}
+
+/// Formats a number of seconds like a stopwatch, as minutes and seconds
+fn format_clock(seconds: f32) -> String {
+    let total_seconds = seconds.max(0.0) as u32;
+    format!("{:02}:{:02}", total_seconds / 60, total_seconds % 60)
+}
+
+/// Puts the camera and physics back to normal after a death sequence
+fn end_death_sequence(
+    mut commands: Commands,
+    mut death_sequence: ResMut<DeathSequence>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+    mut camera_query: Query<(Entity, &mut Transform), With<MainCamera>>,
+) {
+    death_sequence.0 = None;
+    rapier_config.timestep_mode = TimestepMode::VariableTimestep;
+
+    for (entity, mut transform) in camera_query.iter_mut() {
+        commands.entity(entity).remove::<Animator<Transform>>();
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+        transform.scale = Vec3::ONE;
+    }
+}
+
+fn show_game_over_screen(
+    commands: &mut Commands,
+    result: GameResult,
+    best: Option<Score>,
+    panel_color: Color,
+    localization: &Localization,
+    ui_scale: f32,
+    asset_server: Res<AssetServer>,
+) {
+    let headline = match result.reason {
+        GameOverReason::PhoneFell => localization.localize("game_over.phone_fell"),
+        GameOverReason::TooManyMisses => localization.localize("game_over.too_many_misses"),
+        GameOverReason::Panicked => localization.localize("game_over.panicked"),
+        GameOverReason::GotUp => localization.localize("game_over.got_up"),
+        GameOverReason::FellAsleep => localization.localize("game_over.fell_asleep"),
+        GameOverReason::Overslept => localization.localize("game_over.overslept"),
+        GameOverReason::FinallyUp => localization.localize("game_over.finally_up"),
+        GameOverReason::LateForWork => localization.localize("game_over.late_for_work"),
+    };
+    let mut lines = vec![
+        headline.to_string(),
+        localization.localize_with(
+            "game_over.summary",
+            &[
+                ("time", result.final_time.to_string()),
+                ("snoozes", result.snoozes.to_string()),
+            ],
+        ),
+    ];
+    if let Some(best) = best {
+        lines.push(
+            localization.localize_with("game_over.best", &[("snoozes", best.snoozes.to_string())]),
+        );
+    }
+    if let Some(minutes) = result.minutes_past_goal() {
+        lines.push(localization.localize_with(
+            "game_over.minutes_late",
+            &[("minutes", minutes.to_string())],
+        ));
+    }
+    lines.push(localization.localize_with(
+        "game_over.real_time",
+        &[("time", format_clock(result.real_seconds))],
+    ));
+    lines.push(localization.localize_with("game_over.seed", &[("seed", result.seed.to_string())]));
+    let text = lines.join("\n");
+
+    let font = asset_server.load(MAIN_FONT);
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(80.0), Val::Percent(40.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Percent(30.0),
+                    left: Val::Percent(10.0),
+                    ..Default::default()
+                },
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(panel_color),
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: text,
+                        style: TextStyle {
+                            font: font.clone(),
+                            font_size: 30.0 * ui_scale,
+                            color: Color::WHITE,
+                        },
+                    }],
+                    alignment: TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        vertical: VerticalAlign::Center,
+                    },
+                },
+                style: Style {
+                    align_self: AlignSelf::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    color: UiColor(Color::NONE),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    spawn_button(
+                        parent,
+                        font.clone(),
+                        ui_scale,
+                        localization.localize("game_over.try_again"),
+                        TryAgainButton,
+                    );
+                    spawn_button(
+                        parent,
+                        font.clone(),
+                        ui_scale,
+                        localization.localize("game_over.menu"),
+                        GameOverMenuButton,
+                    );
+                });
+        });
+}
+
+/// Handles interactions with the try again button. Everything for the run gets reset on the way back into the game.
+fn try_again_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<TryAgainButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::GameLoading).unwrap();
+        }
+    }
+}
+
+/// Handles interactions with the button for going back to the menu from the game over screen.
+fn game_over_menu_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<GameOverMenuButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Menu).unwrap();
+        }
+    }
+}
+
+/// The alarm sound to loop after the provided number of snoozes, which gets more urgent the more the player snoozes
+fn alarm_sound(num_snoozes: u32) -> &'static str {
+    if num_snoozes >= FRANTIC_ALARM_SNOOZES {
+        FRANTIC_ALARM_SOUND
+    } else if num_snoozes >= LOUDER_ALARM_SNOOZES {
+        LOUDER_ALARM_SOUND
+    } else {
+        ALARM_SOUND
+    }
+}
+
+/// Handles starting and stopping the alarm sound. This is the only thing that plays it, both when the run starts and
+/// after each sleep, so the clip always matches how many snoozes there have been.
+fn alarm_sound_system(
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    save_data: Res<SaveData>,
+    alarm_active: Res<AlarmActive>,
+    num_snoozes: Res<NumSnoozes>,
+    alarm_channel: Res<AlarmChannel>,
+) {
+    if alarm_active.is_changed() {
+        // stop whatever was playing first, so there's never more than one alarm going at once
+        audio.stop_channel(&alarm_channel.0);
+        if alarm_active.0 {
+            audio.set_playback_rate_in_channel(
+                save_data.settings.alarm_tone.playback_rate(),
+                &alarm_channel.0,
+            );
+            audio.play_looped_in_channel(
+                asset_server.load(alarm_sound(num_snoozes.0)),
+                &alarm_channel.0,
+            );
+        }
+    }
+}
+
+/// Puts the alarm to rest when leaving a run, so nothing waiting on it keeps counting outside the game
+fn leave_run_system(
+    mut alarm_active: ResMut<AlarmActive>,
+    mut idle: ResMut<Idle>,
+    mut snooze_assist: ResMut<SnoozeAssist>,
+) {
+    alarm_active.0 = false;
+    *idle = Idle::default();
+    snooze_assist.available = false;
+}
+
+/// Makes sure the alarm doesn't keep going after leaving a run early, like by quitting to the menu while paused
+fn stop_alarm(audio: Res<Audio>, alarm_channel: Res<AlarmChannel>) {
+    audio.stop_channel(&alarm_channel.0);
+}
+
+/// Plays a one-off sound effect at the volume from the settings
+fn play_sfx(audio: &Audio, sound: Handle<AudioSource>, settings: &Settings) {
+    let sfx_channel = AudioChannel::new(SFX_CHANNEL.to_string());
+    audio.set_volume_in_channel(settings.master_volume * settings.sfx_volume, &sfx_channel);
+    audio.play_in_channel(sound, &sfx_channel);
+}
+
+/// The playback rate for a sound effect played with the provided snooze streak going
+fn combo_pitch(streak: u32) -> f32 {
+    (1.0 + streak as f32 * COMBO_PITCH_STEP).min(MAX_COMBO_PITCH)
+}
+
+/// Plays a one-off sound effect in the provided channel, at the volume from the settings and the provided playback rate.
+/// The playback rate applies to the whole channel, so each pitched sound effect should get its own.
+fn play_pitched_sfx(
+    audio: &Audio,
+    sound: Handle<AudioSource>,
+    channel: &str,
+    pitch: f32,
+    settings: &Settings,
+) {
+    let channel = AudioChannel::new(channel.to_string());
+    audio.set_volume_in_channel(settings.master_volume * settings.sfx_volume, &channel);
+    audio.set_playback_rate_in_channel(pitch, &channel);
+    audio.play_in_channel(sound, &channel);
+}
+
+/// Handles silencing all the audio when the app is closing
+fn exit_audio_system(
+    audio: Res<Audio>,
+    alarm_channel: Res<AlarmChannel>,
+    mut close_events: EventReader<WindowCloseRequested>,
+    mut exit_events: EventReader<AppExit>,
+) {
+    if close_events.iter().next().is_some() || exit_events.iter().next().is_some() {
+        audio.stop_channel(&alarm_channel.0);
+        audio.stop_channel(&AudioChannel::new(SFX_CHANNEL.to_string()));
+        audio.stop_channel(&AudioChannel::new(COMBO_SFX_CHANNEL.to_string()));
+        audio.stop_channel(&AudioChannel::new(MUSIC_CHANNEL.to_string()));
+        audio.stop();
+    }
+}
+
+/// Handles showing hints about which way the arm extends, until the player gets the hang of it
+fn arm_hint_system(
+    time: Res<Time>,
+    action_state: Res<ActionState>,
+    mut arm_hint_timer: ResMut<ArmHintTimer>,
+    mut arm_hint_uses: ResMut<ArmHintUses>,
+    mut hint_query: Query<&mut Text, With<ArmHint>>,
+) {
+    arm_hint_timer.0.tick(time.delta());
+
+    if action_state.just_pressed(InputAction::ExtendArm)
+        || action_state.just_pressed(InputAction::RetractArm)
+    {
+        arm_hint_uses.0 += 1;
+    }
+
+    let extend_held = action_state.pressed(InputAction::ExtendArm);
+    let retract_held = action_state.pressed(InputAction::RetractArm);
+    let showing = arm_hint_uses.0 < ARM_HINT_MAX_USES
+        && (!arm_hint_timer.0.finished() || extend_held || retract_held);
+    let target_alpha = if showing { 1.0 } else { 0.0 };
+    let fade_amount = (time.delta_seconds() * ARM_HINT_FADE_SPEED).min(1.0);
+
+    for mut text in hint_query.iter_mut() {
+        for section in text.sections.iter_mut() {
+            let alpha = section.style.color.a();
+            section.style.color =
+                Color::rgba(1.0, 1.0, 1.0, alpha + (target_alpha - alpha) * fade_amount);
+        }
+    }
+}
+
+/// Handles leaving a fading trail behind the phone when it's moving fast
+fn phone_trail_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    save_data: Res<SaveData>,
+    image_assets: Res<ImageAssets>,
+    mut phone_trail: ResMut<PhoneTrail>,
+    phone_query: Query<&Transform, With<Phone>>,
+    trail_query: Query<Entity, With<TrailSprite>>,
+) {
+    let transform = match phone_query.iter().next() {
+        Some(transform) => transform,
+        None => {
+            phone_trail.last_position = None;
+            return;
+        }
+    };
+
+    let last_position = phone_trail.last_position.replace(transform.translation);
+    phone_trail.spawn_timer.tick(time.delta());
+
+    if !save_data.settings.phone_trail || save_data.settings.reduce_motion {
+        return;
+    }
+
+    let speed = match last_position {
+        Some(last_position) if time.delta_seconds() > 0.0 => {
+            last_position.distance(transform.translation) / time.delta_seconds()
+        }
+        _ => 0.0,
+    };
+
+    if speed < TRAIL_MIN_SPEED
+        || !phone_trail.spawn_timer.finished()
+        || trail_query.iter().count() >= MAX_TRAIL_SPRITES
+    {
+        return;
+    }
+
+    let trail_color = with_alpha(save_data.settings.phone_skin.tint(), TRAIL_STARTING_ALPHA);
+    let fade_tween = Tween::new(
+        EaseFunction::QuadraticOut,
+        TweeningType::Once,
+        TRAIL_FADE_TIME,
+        SpriteColorLens {
+            start: trail_color,
+            end: Color::rgba(1.0, 1.0, 1.0, 0.0),
+        },
+    )
+    .with_completed_event(true, TRAIL_TWEEN_COMPLETED);
+
+    commands
+        .spawn_bundle(SpriteBundle {
+            texture: image_assets.phone.clone(),
+            sprite: Sprite {
+                color: trail_color,
+                ..Default::default()
+            },
+            transform: Transform {
+                // just behind the phone
+                translation: transform.translation - Vec3::new(0.0, 0.0, 0.5),
+                ..*transform
+            },
+            ..Default::default()
+        })
+        .insert(Animator::new(fade_tween))
+        .insert(GameComponent)
+        .insert(TrailSprite);
+}
+
+/// Handles despawning trail sprites once they've faded out
+fn trail_cleanup_system(mut commands: Commands, mut event_reader: EventReader<TweenCompleted>) {
+    for event in event_reader.iter() {
+        if event.user_data == TRAIL_TWEEN_COMPLETED {
+            commands.entity(event.entity).despawn_recursive();
+        }
+    }
+}
+
+type ArmPartQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut RigidBodyPositionComponent,
+        &'static mut RigidBodyVelocityComponent,
+        Option<&'static Arm>,
+        Option<&'static Hand>,
+    ),
+    Or<(With<ArmAnchor>, With<Arm>, With<Hand>)>,
+>;
+
+/// Moves the arm anchor, arm, and hand back to where they started, and stops them moving
+fn reset_arm_pose(
+    starting_pose: &StartingPose,
+    rest_positions: &RestPositions,
+    arm_part_query: &mut ArmPartQuery,
+) {
+    for (mut position, mut velocity, arm, hand) in arm_part_query.iter_mut() {
+        let (translation, angle) = if arm.is_some() {
+            (rest_positions.arm.truncate(), starting_pose.arm_angle)
+        } else if hand.is_some() {
+            (rest_positions.hand.truncate(), starting_pose.hand_angle)
+        } else {
+            (
+                Vec2::new(starting_pose.arm_anchor_x, starting_pose.arm_anchor_y),
+                0.0,
+            )
+        };
+
+        position.position = Isometry::new(Vector::new(translation.x, translation.y), angle);
+        position.next_position = position.position;
+        velocity.linvel = Vector::zeros();
+        velocity.angvel = 0.0;
+    }
+}
+
+/// Handles the emergency reset key, which puts the arm back where it started at the cost of some time
+fn emergency_reset_system(
+    action_state: Res<ActionState>,
+    input_allowed: Res<InputAllowed>,
+    difficulty: Res<Difficulty>,
+    starting_pose: Res<StartingPose>,
+    rest_positions: Res<RestPositions>,
+    mut time: ResMut<GameTime>,
+    mut time_display_query: Query<&mut Text, With<TimeDisplay>>,
+    mut arm_part_query: ArmPartQuery,
+) {
+    if !input_allowed.0 || !action_state.just_pressed(InputAction::ResetArm) {
+        return;
+    }
+
+    let penalty_minutes = match difficulty.reset_penalty_minutes() {
+        Some(minutes) => minutes,
+        // no bailouts on this difficulty
+        None => return,
+    };
+
+    reset_arm_pose(&starting_pose, &rest_positions, &mut arm_part_query);
+
+    time.advance(penalty_minutes);
+    for mut time_text in time_display_query.iter_mut() {
+        time_text.sections[0].value = time.to_string();
+    }
+}
+
+/// Whether the provided arm part's physics have blown up into nonsense
+fn is_unstable(
+    position: &RigidBodyPositionComponent,
+    velocity: &RigidBodyVelocityComponent,
+) -> bool {
+    let translation = position.position.translation;
+    let rotation = position.position.rotation;
+    !translation.x.is_finite()
+        || !translation.y.is_finite()
+        || !rotation.re.is_finite()
+        || !rotation.im.is_finite()
+        || translation.vector.norm() > MAX_SANE_POSITION
+        || !velocity.linvel.norm().is_finite()
+        || velocity.linvel.norm() > MAX_SANE_LINEAR_VELOCITY
+        || !velocity.angvel.is_finite()
+        || velocity.angvel.abs() > MAX_SANE_ANGULAR_VELOCITY
+}
+
+/// Handles putting the arm back where it started if the physics ever blow up, rather than leaving the run stuck.
+/// The parts are all jointed together, so they all get reset even if only one of them went wrong.
+fn physics_recovery_system(
+    starting_pose: Res<StartingPose>,
+    rest_positions: Res<RestPositions>,
+    mut arm_part_query: ArmPartQuery,
+) {
+    let unstable = arm_part_query
+        .iter()
+        .any(|(position, velocity, _, _)| is_unstable(position, velocity));
+    if !unstable {
+        return;
+    }
+
+    warn!("arm physics became unstable, resetting the arm");
+    reset_arm_pose(&starting_pose, &rest_positions, &mut arm_part_query);
+}
+
+#[cfg(test)]
+mod physics_recovery_tests {
+    use super::*;
+
+    fn position(x: f32, y: f32, angle: f32) -> RigidBodyPositionComponent {
+        (Vec2::new(x, y), angle).into()
+    }
+
+    fn velocity(x: f32, y: f32, angvel: f32) -> RigidBodyVelocityComponent {
+        RigidBodyVelocity {
+            linvel: Vec2::new(x, y).into(),
+            angvel,
+        }
+        .into()
+    }
+
+    #[test]
+    fn normal_motion_is_stable() {
+        assert!(!is_unstable(
+            &position(100.0, -50.0, 1.0),
+            &velocity(300.0, -200.0, 5.0)
+        ));
+    }
+
+    #[test]
+    fn nan_is_unstable() {
+        assert!(is_unstable(
+            &position(f32::NAN, 0.0, 0.0),
+            &velocity(0.0, 0.0, 0.0)
+        ));
+        assert!(is_unstable(
+            &position(0.0, 0.0, f32::NAN),
+            &velocity(0.0, 0.0, 0.0)
+        ));
+        assert!(is_unstable(
+            &position(0.0, 0.0, 0.0),
+            &velocity(f32::NAN, 0.0, 0.0)
+        ));
+        assert!(is_unstable(
+            &position(0.0, 0.0, 0.0),
+            &velocity(0.0, 0.0, f32::NAN)
+        ));
+    }
+
+    #[test]
+    fn infinity_is_unstable() {
+        assert!(is_unstable(
+            &position(0.0, f32::INFINITY, 0.0),
+            &velocity(0.0, 0.0, 0.0)
+        ));
+        assert!(is_unstable(
+            &position(0.0, 0.0, 0.0),
+            &velocity(0.0, f32::NEG_INFINITY, 0.0)
+        ));
+        assert!(is_unstable(
+            &position(0.0, 0.0, 0.0),
+            &velocity(0.0, 0.0, f32::INFINITY)
+        ));
+    }
+
+    #[test]
+    fn huge_values_are_unstable() {
+        assert!(is_unstable(
+            &position(MAX_SANE_POSITION * 2.0, 0.0, 0.0),
+            &velocity(0.0, 0.0, 0.0)
+        ));
+        assert!(is_unstable(
+            &position(0.0, 0.0, 0.0),
+            &velocity(MAX_SANE_LINEAR_VELOCITY * 2.0, 0.0, 0.0)
+        ));
+        assert!(is_unstable(
+            &position(0.0, 0.0, 0.0),
+            &velocity(0.0, 0.0, -MAX_SANE_ANGULAR_VELOCITY * 2.0)
+        ));
+    }
+
+    fn world_with_starting_pose() -> World {
+        let mut world = World::new();
+        world.insert_resource(StartingPose {
+            arm_anchor_x: 1000.0,
+            arm_anchor_y: -100.0,
+            arm_angle: 0.5,
+            hand_angle: -0.5,
+        });
+        world.insert_resource(RestPositions {
+            arm: Vec3::new(500.0, -100.0, 0.0),
+            hand: Vec3::new(100.0, -100.0, 0.0),
+        });
+
+        world
+    }
+
+    #[test]
+    fn nan_velocity_gets_the_arm_reset() {
+        let mut world = world_with_starting_pose();
+        let anchor = world
+            .spawn()
+            .insert_bundle((
+                position(1200.0, -100.0, 0.0),
+                velocity(0.0, 0.0, 0.0),
+                ArmAnchor,
+            ))
+            .id();
+        let arm = world
+            .spawn()
+            .insert_bundle((position(600.0, -80.0, 0.2), velocity(10.0, 0.0, 0.0), Arm))
+            .id();
+        let hand = world
+            .spawn()
+            .insert_bundle((
+                position(150.0, -90.0, 0.0),
+                velocity(f32::NAN, 0.0, 0.0),
+                Hand,
+            ))
+            .id();
+
+        let mut stage = SystemStage::single(physics_recovery_system);
+        stage.run(&mut world);
+
+        let hand_position = world.get::<RigidBodyPositionComponent>(hand).unwrap();
+        assert_eq!(100.0, hand_position.position.translation.x);
+        assert_eq!(-100.0, hand_position.position.translation.y);
+        let hand_velocity = world.get::<RigidBodyVelocityComponent>(hand).unwrap();
+        assert_eq!(0.0, hand_velocity.linvel.x);
+        assert_eq!(0.0, hand_velocity.angvel);
+
+        // the rest of the arm is jointed to the hand, so it gets reset too
+        let arm_position = world.get::<RigidBodyPositionComponent>(arm).unwrap();
+        assert_eq!(500.0, arm_position.position.translation.x);
+        assert_eq!(
+            0.0,
+            world
+                .get::<RigidBodyVelocityComponent>(arm)
+                .unwrap()
+                .linvel
+                .x
+        );
+        let anchor_position = world.get::<RigidBodyPositionComponent>(anchor).unwrap();
+        assert_eq!(1000.0, anchor_position.position.translation.x);
+    }
+
+    #[test]
+    fn stable_arm_is_left_alone() {
+        let mut world = world_with_starting_pose();
+        let hand = world
+            .spawn()
+            .insert_bundle((position(150.0, -90.0, 0.0), velocity(50.0, 0.0, 0.0), Hand))
+            .id();
+
+        let mut stage = SystemStage::single(physics_recovery_system);
+        stage.run(&mut world);
+
+        let hand_position = world.get::<RigidBodyPositionComponent>(hand).unwrap();
+        assert_eq!(150.0, hand_position.position.translation.x);
+        assert_eq!(
+            50.0,
+            world
+                .get::<RigidBodyVelocityComponent>(hand)
+                .unwrap()
+                .linvel
+                .x
+        );
+    }
+}
+
+/// Applies the physics quality from the settings
+fn physics_quality_setup(
+    save_data: Res<SaveData>,
+    mut integration_parameters: ResMut<IntegrationParameters>,
+) {
+    integration_parameters.max_velocity_iterations = save_data.settings.physics_iterations();
+}
+
+/// Handles sliding the phone across the table while the alarm is going. The vibration tweens set the phone's position
+/// outright, so they carry the slide along themselves while they're running.
+fn phone_gravity_system(
+    time: Res<Time>,
+    gravity: Res<Gravity>,
+    alarm_active: Res<AlarmActive>,
+    death_sequence: Res<DeathSequence>,
+    mut phone_query: Query<&mut Transform, With<Phone>>,
+) {
+    if gravity.0 == Vec2::ZERO || !alarm_active.0 || death_sequence.is_started() {
+        return;
+    }
+
+    for mut transform in phone_query.iter_mut() {
+        transform.translation += (gravity.0 * time.delta_seconds()).extend(0.0);
+    }
+}
+
+/// Handles tweaking gravity on the fly, for trying out different values
+fn dev_gravity_system(keyboard: Res<Input<KeyCode>>, mut gravity: ResMut<Gravity>) {
+    let mut change = Vec2::ZERO;
+    if keyboard.just_pressed(KeyCode::I) {
+        change.y += DEV_GRAVITY_STEP;
+    }
+    if keyboard.just_pressed(KeyCode::K) {
+        change.y -= DEV_GRAVITY_STEP;
+    }
+    if keyboard.just_pressed(KeyCode::J) {
+        change.x -= DEV_GRAVITY_STEP;
+    }
+    if keyboard.just_pressed(KeyCode::L) {
+        change.x += DEV_GRAVITY_STEP;
+    }
+    if keyboard.just_pressed(KeyCode::O) {
+        // back to normal
+        change = -gravity.0;
+    }
+
+    if change != Vec2::ZERO {
+        gravity.0 += change;
+        info!("gravity is now {}", gravity.0);
+    }
+}
+
+/// Whether the grid and position readouts for lining things up are being shown
+struct CalibrationOverlay(bool);
+
+/// A line of the grid drawn over the scene for lining things up
+#[derive(Component)]
+struct CalibrationGrid;
+
+/// The text showing where the hand, touch areas, and snooze buttons are
+#[derive(Component)]
+struct CalibrationReadout;
+
+/// Sets up the grid and position readouts for lining things up, hidden until they're toggled on
+fn calibration_overlay_setup(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    calibration_overlay: Res<CalibrationOverlay>,
+) {
+    let lines = (CALIBRATION_GRID_EXTENT / CALIBRATION_GRID_SPACING) as i32;
+    for i in -lines..=lines {
+        let offset = i as f32 * CALIBRATION_GRID_SPACING;
+        let color = if i == 0 {
+            CALIBRATION_AXIS_COLOR
+        } else {
+            CALIBRATION_GRID_COLOR
+        };
+        let line_sizes = [
+            (
+                Vec3::new(offset, 0.0, CALIBRATION_GRID_Z),
+                Vec2::new(1.0, CALIBRATION_GRID_EXTENT * 2.0),
+            ),
+            (
+                Vec3::new(0.0, offset, CALIBRATION_GRID_Z),
+                Vec2::new(CALIBRATION_GRID_EXTENT * 2.0, 1.0),
+            ),
+        ];
+        for (translation, size) in line_sizes {
+            commands
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(size),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(translation),
+                    visibility: Visibility {
+                        is_visible: calibration_overlay.0,
+                    },
+                    ..Default::default()
+                })
+                .insert(GameComponent)
+                .insert(CalibrationGrid);
+        }
+    }
+
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 16.0,
+                    color: CALIBRATION_AXIS_COLOR,
+                },
+                TextAlignment::default(),
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(15.0),
+                    left: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            visibility: Visibility {
+                is_visible: calibration_overlay.0,
+            },
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(CalibrationReadout);
+}
+
+/// Handles turning the calibration overlay on and off
+fn calibration_overlay_toggle_system(
+    keyboard: Res<Input<KeyCode>>,
+    mut calibration_overlay: ResMut<CalibrationOverlay>,
+    mut visibility_query: Query<
+        &mut Visibility,
+        Or<(With<CalibrationGrid>, With<CalibrationReadout>)>,
+    >,
+) {
+    if !keyboard.just_pressed(CALIBRATION_OVERLAY_KEY) {
+        return;
+    }
+
+    calibration_overlay.0 = !calibration_overlay.0;
+    for mut visibility in visibility_query.iter_mut() {
+        visibility.is_visible = calibration_overlay.0;
+    }
+}
+
+/// Describes where something is, for the calibration readout
+fn calibration_position_text(name: &str, transform: &GlobalTransform) -> String {
+    let (axis, angle) = transform.rotation.to_axis_angle();
+    format!(
+        "{name}: ({:.1}, {:.1}) {:.1} deg",
+        transform.translation.x,
+        transform.translation.y,
+        (angle * axis.z.signum()).to_degrees()
+    )
+}
+
+/// Handles keeping the calibration readout up to date with where everything is
+fn calibration_readout_system(
+    calibration_overlay: Res<CalibrationOverlay>,
+    drift_samples: Res<DriftSamples>,
+    hand_query: Query<&GlobalTransform, With<Hand>>,
+    touch_area_query: Query<&GlobalTransform, With<TouchArea>>,
+    snooze_button_query: Query<(&SnoozeButton, &GlobalTransform)>,
+    mut readout_query: Query<&mut Text, With<CalibrationReadout>>,
+) {
+    if !calibration_overlay.0 {
+        return;
+    }
+
+    let mut lines = Vec::new();
+    for transform in hand_query.iter() {
+        lines.push(calibration_position_text("hand", transform));
+    }
+    for (i, transform) in touch_area_query.iter().enumerate() {
+        lines.push(calibration_position_text(
+            &format!("touch area {i}"),
+            transform,
+        ));
+    }
+    let mut snooze_buttons = snooze_button_query.iter().collect::<Vec<_>>();
+    snooze_buttons.sort_by_key(|(button, _)| button.0);
+    for (button, transform) in snooze_buttons {
+        lines.push(calibration_position_text(
+            &format!("snooze button {}", button.0),
+            transform,
+        ));
+    }
+
+    lines.push(format!(
+        "max drift this alarm: {:.1}",
+        drift_samples.max().unwrap_or(0.0)
+    ));
+
+    let text = lines.join("\n");
+    for mut readout in readout_query.iter_mut() {
+        readout.sections[0].value = text.clone();
+    }
+}
+
+/// How far the phone has been from the middle of the table over the course of the current alarm, sampled every frame
+#[derive(Default)]
+struct DriftSamples(Vec<f32>);
+
+impl DriftSamples {
+    /// The furthest the phone has drifted this alarm, if it's been sampled at all
+    fn max(&self) -> Option<f32> {
+        self.0.iter().copied().reduce(f32::max)
+    }
+
+    /// The closest the phone has been to the middle this alarm, if it's been sampled at all
+    fn min(&self) -> Option<f32> {
+        self.0.iter().copied().reduce(f32::min)
+    }
+
+    /// How far the phone has been from the middle on average this alarm, if it's been sampled at all
+    fn average(&self) -> Option<f32> {
+        if self.0.is_empty() {
+            None
+        } else {
+            Some(self.0.iter().sum::<f32>() / self.0.len() as f32)
+        }
+    }
+}
+
+/// Throws out the drift samples from the last run
+fn drift_samples_reset_system(mut drift_samples: ResMut<DriftSamples>) {
+    drift_samples.0.clear();
+}
+
+/// Handles recording how far the phone is from the middle of the table while the alarm is going off
+fn drift_sample_system(
+    paused: Res<Paused>,
+    alarm_active: Res<AlarmActive>,
+    mut drift_samples: ResMut<DriftSamples>,
+    phone_query: Query<&Transform, With<Phone>>,
+) {
+    if paused.is_paused() || !alarm_active.0 {
+        return;
+    }
+
+    for transform in phone_query.iter() {
+        drift_samples
+            .0
+            .push(transform.translation.truncate().length());
+    }
+}
+
+/// Handles logging how far the phone drifted during each alarm once it's snoozed, for tuning the table bounds against the vibration strength
+fn drift_summary_system(
+    mut drift_samples: ResMut<DriftSamples>,
+    mut snooze_events: EventReader<SnoozeEvent>,
+) {
+    if snooze_events.iter().count() == 0 {
+        return;
+    }
+
+    if let (Some(min), Some(max), Some(average)) = (
+        drift_samples.min(),
+        drift_samples.max(),
+        drift_samples.average(),
+    ) {
+        info!(
+            "phone drift this alarm: min {min:.1}, max {max:.1}, avg {average:.1} over {} samples",
+            drift_samples.0.len()
+        );
+    }
+    drift_samples.0.clear();
+}
+
+/// Sets up the display of how many misses are left, if there's a limit
+fn misses_display_setup(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    miss_limit: Res<MissLimit>,
+    localization: Res<Localization>,
+    save_data: Res<SaveData>,
+) {
+    let limit = match miss_limit.0 {
+        Some(limit) => limit,
+        None => return,
+    };
+    let ui_scale = save_data.settings.ui_scale();
+
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                misses_left_text(limit, 0, &localization),
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 30.0 * ui_scale,
+                    color: Color::WHITE,
+                },
+                TextAlignment::default(),
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(15.0),
+                    left: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(MissesDisplay);
+}
+
+/// Sets up the deadline for the run from the difficulty, in modes that have one
+fn deadline_setup(
+    difficulty: Res<Difficulty>,
+    game_mode: Res<GameMode>,
+    mut deadline: ResMut<Deadline>,
+) {
+    deadline.0 = if game_mode.has_deadline() {
+        difficulty.deadline()
+    } else {
+        None
+    };
+}
+
+/// Sets up the display of how long is left until the deadline, if there is one
+fn deadline_display_setup(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    deadline: Res<Deadline>,
+    localization: Res<Localization>,
+    save_data: Res<SaveData>,
+) {
+    let deadline = match deadline.0 {
+        Some(deadline) => deadline,
+        None => return,
+    };
+    let ui_scale = save_data.settings.ui_scale();
+
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                deadline_text(&STARTING_TIME, &deadline, &localization),
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 30.0 * ui_scale,
+                    color: Color::WHITE,
+                },
+                TextAlignment::default(),
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(15.0 + 40.0 * ui_scale),
+                    left: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(DeadlineDisplay);
+}
+
+fn deadline_text(time: &GameTime, deadline: &GameTime, localization: &Localization) -> String {
+    localization.localize_with(
+        "hud.deadline",
+        &[("minutes", deadline.minutes_past(time).max(0).to_string())],
+    )
+}
+
+/// Handles counting down to the deadline, and ending the run if the player snoozes past it.
+/// The run doesn't end until the screen has faded back in, so the player gets to see what time it is.
+fn deadline_system(
+    game_time: Res<GameTime>,
+    deadline: Res<Deadline>,
+    input_allowed: Res<InputAllowed>,
+    alarm_active: Res<AlarmActive>,
+    death_sequence: Res<DeathSequence>,
+    localization: Res<Localization>,
+    mut deadline_display_query: Query<&mut Text, With<DeadlineDisplay>>,
+    mut game_over_events: EventWriter<GameOverEvent>,
+) {
+    let deadline = match deadline.0 {
+        Some(deadline) => deadline,
+        None => return,
+    };
+
+    if game_time.is_changed() {
+        for mut text in deadline_display_query.iter_mut() {
+            text.sections[0].value = deadline_text(&game_time, &deadline, &localization);
+        }
+    }
+
+    if !input_allowed.0 || !alarm_active.0 || death_sequence.is_started() {
+        return;
+    }
+
+    if game_time.minutes_past(&deadline) > 0 {
+        // should've left already
+        game_over_events.send(GameOverEvent {
+            reason: GameOverReason::LateForWork,
+        });
+    }
+}
+
+fn misses_left_text(limit: u32, misses: u32, localization: &Localization) -> String {
+    localization.localize_with(
+        "hud.misses_left",
+        &[("misses", limit.saturating_sub(misses).to_string())],
+    )
+}
+
+/// Handles ending the run once the player has missed too many times
+fn miss_limit_system(
+    miss_count: Res<MissCount>,
+    miss_limit: Res<MissLimit>,
+    death_sequence: Res<DeathSequence>,
+    localization: Res<Localization>,
+    mut misses_display_query: Query<&mut Text, With<MissesDisplay>>,
+    mut game_over_events: EventWriter<GameOverEvent>,
+) {
+    if !miss_count.is_changed() {
+        return;
+    }
+
+    let limit = match miss_limit.0 {
+        Some(limit) => limit,
+        None => return,
+    };
+
+    for mut text in misses_display_query.iter_mut() {
+        text.sections[0].value = misses_left_text(limit, miss_count.0, &localization);
+    }
+
+    if miss_count.0 >= limit && death_sequence.0.is_none() {
+        // that's enough of that
+        game_over_events.send(GameOverEvent {
+            reason: GameOverReason::TooManyMisses,
+        });
+    }
+}
+
+/// Handles ending the run if the player flails the arm around too wildly, on difficulties where that's a thing
+fn shake_system(
+    time: Res<Time>,
+    mut shake_meter: ResMut<ShakeMeter>,
+    death_sequence: Res<DeathSequence>,
+    velocity_query: Query<&RigidBodyVelocityComponent, Or<(With<Arm>, With<Hand>)>>,
+    mut game_over_events: EventWriter<GameOverEvent>,
+) {
+    let limit = match shake_meter.limit {
+        Some(limit) => limit,
+        None => return,
+    };
+
+    let delta_seconds = time.delta_seconds();
+    let spin = velocity_query
+        .iter()
+        .map(|velocity| velocity.angvel.abs())
+        .sum::<f32>();
+    shake_meter.amount =
+        shake_meter.amount * (1.0 - SHAKE_DECAY * delta_seconds).max(0.0) + spin * delta_seconds;
+
+    if shake_meter.amount >= limit && death_sequence.0.is_none() {
+        // calm down
+        game_over_events.send(GameOverEvent {
+            reason: GameOverReason::Panicked,
+        });
+    }
+}
+
+/// Sets up the display of the run's seed, so it can be shared
+fn seed_display_setup(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    rng: Res<GameRng>,
+    localization: Res<Localization>,
+    save_data: Res<SaveData>,
+) {
+    let ui_scale = save_data.settings.ui_scale();
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                localization.localize_with("hud.seed", &[("seed", rng.seed.to_string())]),
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 20.0 * ui_scale,
+                    color: Color::GRAY,
+                },
+                TextAlignment::default(),
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    bottom: Val::Px(15.0),
+                    left: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(SeedDisplay);
+}
+
+/// The text for the snooze counter on the phone
+fn snooze_counter_text(snoozes: u32, localization: &Localization) -> String {
+    localization.localize_with("hud.snooze_count", &[("snoozes", snoozes.to_string())])
+}
+
+/// Handles keeping the snooze counter on the phone up to date. The counter goes away with the phone, so there may not
+/// be one to update.
+fn snooze_counter_system(
+    num_snoozes: Res<NumSnoozes>,
+    localization: Res<Localization>,
+    mut snooze_counter_query: Query<&mut Text, With<SnoozeCounter>>,
+) {
+    if !num_snoozes.is_changed() {
+        return;
+    }
+
+    for mut text in snooze_counter_query.iter_mut() {
+        text.sections[0].value = snooze_counter_text(num_snoozes.0, &localization);
+    }
+}
+
+fn streak_text(
+    snooze_reward: &SnoozeReward,
+    snooze_streak: &SnoozeStreak,
+    localization: &Localization,
+) -> String {
+    localization.localize_with(
+        "hud.streak",
+        &[
+            ("streak", snooze_streak.0.to_string()),
+            (
+                "minutes",
+                snooze_reward.minutes(snooze_streak.0).to_string(),
+            ),
+        ],
+    )
+}
+
+/// Sets up the display of the current snooze streak, if streaks grant extra sleep
+fn streak_display_setup(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    snooze_reward: Res<SnoozeReward>,
+    snooze_streak: Res<SnoozeStreak>,
+    localization: Res<Localization>,
+    save_data: Res<SaveData>,
+) {
+    if !snooze_reward.scales() {
+        return;
+    }
+    let ui_scale = save_data.settings.ui_scale();
+
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                streak_text(&snooze_reward, &snooze_streak, &localization),
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 30.0 * ui_scale,
+                    color: Color::WHITE,
+                },
+                TextAlignment::default(),
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(15.0),
+                    right: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(StreakDisplay);
+}
+
+/// Handles keeping the snooze streak display up to date
+fn streak_display_system(
+    snooze_reward: Res<SnoozeReward>,
+    snooze_streak: Res<SnoozeStreak>,
+    localization: Res<Localization>,
+    mut streak_display_query: Query<&mut Text, With<StreakDisplay>>,
+) {
+    if !snooze_streak.is_changed() {
+        return;
+    }
+
+    for mut text in streak_display_query.iter_mut() {
+        text.sections[0].value = streak_text(&snooze_reward, &snooze_streak, &localization);
+    }
+}
+
+/// The text for the preview of what time it'll be after the next snooze
+fn snooze_preview_text(
+    time: &GameTime,
+    snooze_reward: &SnoozeReward,
+    snooze_streak: &SnoozeStreak,
+    localization: &Localization,
+) -> String {
+    let mut next_time = *time;
+    next_time.advance(snooze_reward.minutes(snooze_streak.0));
+    localization.localize_with("hud.snooze_preview", &[("time", next_time.to_string())])
+}
+
+/// Sets up the preview of what time it'll be after the next snooze, if the player wants it
+fn snooze_preview_setup(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    time: Res<GameTime>,
+    snooze_reward: Res<SnoozeReward>,
+    snooze_streak: Res<SnoozeStreak>,
+    localization: Res<Localization>,
+    save_data: Res<SaveData>,
+) {
+    if !save_data.settings.snooze_preview {
+        return;
+    }
+    let ui_scale = save_data.settings.ui_scale();
+
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                snooze_preview_text(&time, &snooze_reward, &snooze_streak, &localization),
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 25.0 * ui_scale,
+                    color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+                },
+                TextAlignment::default(),
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(15.0 + 40.0 * ui_scale),
+                    right: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(SnoozePreview);
+}
+
+/// Sets up how long the player can ignore the alarm for this run
+fn idle_setup(save_data: Res<SaveData>, difficulty: Res<Difficulty>, mut idle: ResMut<Idle>) {
+    *idle = Idle {
+        seconds: 0.0,
+        timeout: save_data.settings.idle_timeout_seconds.max(0.0),
+        loss_seconds: difficulty.idle_loss_seconds(),
+    };
+}
+
+/// Handles keeping track of how long the alarm has been going without the player doing anything, and ending the run if it's been too long
+fn idle_system(
+    time: Res<Time>,
+    alarm_active: Res<AlarmActive>,
+    action_state: Res<ActionState>,
+    death_sequence: Res<DeathSequence>,
+    mut idle: ResMut<Idle>,
+    mut game_over_events: EventWriter<GameOverEvent>,
+) {
+    if !alarm_active.0 || action_state.any_pressed() {
+        if idle.seconds > 0.0 {
+            idle.seconds = 0.0;
+        }
+        return;
+    }
+
+    idle.seconds += time.delta_seconds();
+
+    if let Some(loss_seconds) = idle.loss_seconds {
+        if idle.seconds >= loss_seconds && death_sequence.0.is_none() {
+            // zzz
+            game_over_events.send(GameOverEvent {
+                reason: GameOverReason::FellAsleep,
+            });
+        }
+    }
+}
+
+/// Handles ending the run once the player is awake again after their last snooze, in modes with a snooze goal.
+/// Sleeping past the wake goal on the way there loses instead.
+fn win_check_system(
+    game_mode: Res<GameMode>,
+    num_snoozes: Res<NumSnoozes>,
+    game_time: Res<GameTime>,
+    wake_goal: Res<WakeGoal>,
+    input_allowed: Res<InputAllowed>,
+    alarm_active: Res<AlarmActive>,
+    death_sequence: Res<DeathSequence>,
+    mut game_over_events: EventWriter<GameOverEvent>,
 ) {
-    if !input_allowed.0 {
+    let snooze_goal = match game_mode.snooze_goal() {
+        Some(goal) => goal,
+        None => return,
+    };
+
+    // wait until the screen has faded back in from the last snooze
+    if !input_allowed.0 || !alarm_active.0 || death_sequence.is_started() {
         return;
     }
 
-    if keyboard.just_pressed(PRESS_KEY) {
-        audio.play(asset_server.load(HIT_SOUND));
-        if valid_press_position.0 {
-            // gotcha
-            println!("you pressed snooze"); //TODO
-            event_writer.send(SnoozeEvent);
-        } else {
-            // and that's a bad miss
-            println!("you missed"); //TODO
-            input_allowed.0 = false;
-            miss_timer.0 = Timer::from_seconds(MISS_PENALTY_SECONDS, false);
-        }
+    if game_time.minutes_past(&wake_goal.0) > 0 {
+        game_over_events.send(GameOverEvent {
+            reason: GameOverReason::Overslept,
+        });
+    } else if num_snoozes.0 >= snooze_goal {
+        game_over_events.send(GameOverEvent {
+            reason: GameOverReason::FinallyUp,
+        });
     }
 }
 
-/// Handles re-enabling input once the miss penalty time has elapsed
-fn miss_penalty_system(
-    mut input_allowed: ResMut<InputAllowed>,
-    mut miss_timer: ResMut<MissTimer>,
-    time: Res<Time>,
+/// Sets up whether the game will snooze for the player this run.
+/// It does for players who haven't ever snoozed on their own yet, or who've asked for extra help.
+fn snooze_assist_setup(save_data: Res<SaveData>, mut snooze_assist: ResMut<SnoozeAssist>) {
+    let stats = &save_data.stats;
+    snooze_assist.available =
+        save_data.settings.extra_help || stats.total_snoozes == stats.assisted_snoozes;
+    snooze_assist.assisting = false;
+    snooze_assist.timer = Timer::from_seconds(SNOOZE_ASSIST_SECONDS, false);
+}
+
+/// Handles keeping track of snoozes, so the game knows how long the alarm has been going and whether the player can snooze on their own
+fn snooze_assist_tracking_system(
+    mut snooze_assist: ResMut<SnoozeAssist>,
+    mut snooze_events: EventReader<SnoozeEvent>,
 ) {
-    if miss_timer.0.tick(time.delta()).just_finished() {
-        input_allowed.0 = true;
+    if snooze_events.iter().count() == 0 {
+        return;
+    }
+
+    if snooze_assist.assisting {
+        snooze_assist.assisting = false;
+    } else {
+        // they've got the hang of it
+        snooze_assist.available = false;
     }
+    snooze_assist.timer.reset();
 }
 
-/// Handles when the snooze button is pressed
-fn snooze_system(
-    mut time: ResMut<GameTime>,
-    mut num_snoozes: ResMut<NumSnoozes>,
-    mut input_allowed: ResMut<InputAllowed>,
-    mut alarm_active: ResMut<AlarmActive>,
-    mut vibrate_timer: ResMut<VibrateTimer>,
-    audio: Res<Audio>,
-    mut event_reader: EventReader<SnoozeEvent>,
-    mut event_writer: EventWriter<FadeEvent>,
+/// Handles snoozing for the player if the alarm has been going for too long, if they need the help
+fn snooze_assist_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    alarm_active: Res<AlarmActive>,
+    font_assets: Res<FontAssets>,
+    localization: Res<Localization>,
+    mut save_data: ResMut<SaveData>,
+    mut snooze_assist: ResMut<SnoozeAssist>,
+    mut event_writer: EventWriter<SnoozeEvent>,
 ) {
-    if event_reader.iter().next().is_none() {
-        // no snoozin
+    if !snooze_assist.available || !alarm_active.0 {
         return;
     }
 
-    // stop playing alarm sound
-    audio.stop_channel(&AudioChannel::new(ALARM_CHANNEL.to_string()));
+    if !snooze_assist.timer.tick(time.delta()).just_finished() {
+        return;
+    }
 
-    // disallow input
-    input_allowed.0 = false;
+    // here, let me get that for you
+    snooze_assist.available = false;
+    snooze_assist.assisting = true;
+    save_data.stats.assisted_snoozes += 1;
+    event_writer.send(SnoozeEvent);
 
-    // turn off the alarm
-    alarm_active.0 = false;
+    let ui_scale = save_data.settings.ui_scale();
+    commands
+        .spawn_bundle(TextBundle {
+            text: Text::with_section(
+                localization.localize("hud.snooze_assist"),
+                TextStyle {
+                    font: font_assets.main.clone(),
+                    font_size: 25.0 * ui_scale,
+                    color: Color::WHITE,
+                },
+                TextAlignment {
+                    horizontal: HorizontalAlign::Center,
+                    ..Default::default()
+                },
+            ),
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    bottom: Val::Px(15.0 + 40.0 * ui_scale),
+                    left: Val::Px(15.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(SnoozeAssistMessage(Timer::from_seconds(
+            SNOOZE_ASSIST_MESSAGE_SECONDS,
+            false,
+        )));
+}
 
-    // increment snooze counter
-    num_snoozes.0 += 1;
+/// Handles taking down the message about the game snoozing for the player once it's been up long enough
+fn snooze_assist_message_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut message_query: Query<(Entity, &mut SnoozeAssistMessage)>,
+) {
+    for (entity, mut message) in message_query.iter_mut() {
+        if message.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
 
-    // update time
-    time.snooze();
+/// Handles keeping the next snooze preview up to date
+fn snooze_preview_system(
+    time: Res<GameTime>,
+    snooze_reward: Res<SnoozeReward>,
+    snooze_streak: Res<SnoozeStreak>,
+    localization: Res<Localization>,
+    mut snooze_preview_query: Query<&mut Text, With<SnoozePreview>>,
+) {
+    if !time.is_changed() && !snooze_reward.is_changed() && !snooze_streak.is_changed() {
+        return;
+    }
 
-    if vibrate_timer.0.duration().as_secs_f32() > VIBRATE_TIME.as_secs_f32() {
-        // a little bit faster now
-        vibrate_timer.0 = Timer::from_seconds(vibrate_timer.0.duration().as_secs_f32() * 0.9, true);
+    for mut text in snooze_preview_query.iter_mut() {
+        text.sections[0].value =
+            snooze_preview_text(&time, &snooze_reward, &snooze_streak, &localization);
     }
+}
 
-    // fade out
-    event_writer.send(FadeEvent(FadeDirection::Out));
+/// The color a snooze button should be, depending on whether it's the one that works
+fn snooze_button_color(active_color: Color, active: bool) -> Color {
+    if active {
+        active_color
+    } else {
+        Color::rgba(
+            active_color.r() * INACTIVE_SNOOZE_BUTTON_DIMMING,
+            active_color.g() * INACTIVE_SNOOZE_BUTTON_DIMMING,
+            active_color.b() * INACTIVE_SNOOZE_BUTTON_DIMMING,
+            active_color.a(),
+        )
+    }
 }
 
-/// Handles updates while the player gets a few minutes of precious sleep
-fn sleep_system(
+/// Handles switching which snooze button works while the screen is faded out
+fn snooze_button_rotation_system(
+    mut rng: ResMut<GameRng>,
+    mut snooze_button_layout: ResMut<SnoozeButtonLayout>,
     mut event_reader: EventReader<TweenCompleted>,
-    mut event_writer: EventWriter<FadeEvent>,
-    time: Res<GameTime>,
-    audio: Res<Audio>,
-    asset_server: Res<AssetServer>,
-    mut input_allowed: ResMut<InputAllowed>,
-    mut alarm_active: ResMut<AlarmActive>,
-    mut time_display_query: Query<&mut Text, With<TimeDisplay>>,
-    mut arm_anchor_query: Query<&mut RigidBodyPositionComponent, With<ArmAnchor>>,
 ) {
     for event in event_reader.iter() {
-        if event.user_data != FADE_OUT_TWEEN_COMPLETED {
+        if event.user_data != FADE_OUT_TWEEN_COMPLETED || snooze_button_layout.count <= 1 {
             continue;
         }
 
-        // update the time display
-        for mut time_text in time_display_query.iter_mut() {
-            time_text.sections[0].value = time.to_string();
-        }
-
-        // move the arm anchor back
-        for mut position in arm_anchor_query.iter_mut() {
-            position.position.translation.x = ARM_ANCHOR_STARTING_POSITION_X;
-            position.position.translation.y = ARM_ANCHOR_STARTING_POSITION_Y;
-        }
-
-        //TODO rotate the arm back
-
-        //TODO wait a few seconds
-
-        // start playing alarm sound
-        audio.play_looped_in_channel(
-            asset_server.load(ALARM_SOUND),
-            &AudioChannel::new(ALARM_CHANNEL.to_string()),
-        );
-
-        // allow input
-        input_allowed.0 = true;
+        // always move it somewhere else
+        let offset = rng.rng.gen_range(1..snooze_button_layout.count);
+        snooze_button_layout.active =
+            (snooze_button_layout.active + offset) % snooze_button_layout.count;
+    }
+}
 
-        // turn on the alarm
-        alarm_active.0 = true;
+/// Handles highlighting the snooze button that works
+fn snooze_button_highlight_system(
+    scene_assets: Option<Res<SceneAssets>>,
+    scene_configs: Res<Assets<SceneConfig>>,
+    snooze_button_layout: Res<SnoozeButtonLayout>,
+    mut snooze_button_query: Query<(&mut Sprite, &SnoozeButton)>,
+) {
+    if !snooze_button_layout.is_changed() {
+        return;
+    }
 
-        // fade in
-        event_writer.send(FadeEvent(FadeDirection::In))
+    // the scene isn't around until the game has loaded
+    let active_color = match scene_assets
+        .as_ref()
+        .and_then(|scene_assets| scene_configs.get(&scene_assets.bedroom))
+    {
+        Some(scene) => config_color(scene.snooze_button.color),
+        None => return,
+    };
+    for (mut sprite, button) in snooze_button_query.iter_mut() {
+        sprite.color = snooze_button_color(active_color, button.0 == snooze_button_layout.active);
     }
 }
 
-/// Handles vibrating the phone around
-fn vibration_system(
-    mut commands: Commands,
-    alarm_active: Res<AlarmActive>,
+/// Handles brightening the snooze button that works while a finger is on it, so it's clear pressing will work.
+/// It brightens quickly, and goes back to normal as soon as the finger leaves.
+fn snooze_highlight_system(
     time: Res<Time>,
-    mut vibrate_timer: ResMut<VibrateTimer>,
-    phone_query: Query<(Entity, &Transform), With<Phone>>,
+    scene_assets: Option<Res<SceneAssets>>,
+    scene_configs: Res<Assets<SceneConfig>>,
+    valid_press_position: Res<ValidPressPosition>,
+    snooze_button_layout: Res<SnoozeButtonLayout>,
+    mut highlight: Local<f32>,
+    mut snooze_button_query: Query<(&mut Sprite, &SnoozeButton)>,
 ) {
-    if !alarm_active.0 {
+    if valid_press_position.0.is_some() {
+        *highlight = (*highlight + time.delta_seconds() / SNOOZE_BUTTON_HIGHLIGHT_SECONDS).min(1.0);
+    } else if *highlight > 0.0 {
+        *highlight = 0.0;
+    } else {
+        // already back to normal
         return;
     }
 
-    if vibrate_timer.0.tick(time.delta()).finished() {
-        for (entity, transform) in phone_query.iter() {
-            vibrate_phone(
-                &mut commands,
-                entity,
-                transform.translation,
-                transform.rotation,
-            );
+    // the scene isn't around until the game has loaded
+    let active_color = match scene_assets
+        .as_ref()
+        .and_then(|scene_assets| scene_configs.get(&scene_assets.bedroom))
+    {
+        Some(scene) => config_color(scene.snooze_button.color),
+        None => return,
+    };
+    let normal: Vec4 = active_color.into();
+    let highlighted = normal.lerp(Vec4::ONE, SNOOZE_BUTTON_HIGHLIGHT);
+    for (mut sprite, button) in snooze_button_query.iter_mut() {
+        if button.0 == snooze_button_layout.active {
+            sprite.color = normal.lerp(highlighted, *highlight).into();
         }
     }
 }
 
-/// Vibrates the phone to a random position
-fn vibrate_phone(
-    commands: &mut Commands,
-    entity: Entity,
-    start_position: Vec3,
-    start_rotation: Quat,
+/// Handles sizing the time display on the phone to match the UI scale
+fn time_display_scale_system(
+    save_data: Res<SaveData>,
+    mut time_display_query: Query<(&mut Transform, ChangeTrackers<TimeDisplay>)>,
 ) {
-    let mut rng = rand::thread_rng();
+    let ui_scale = save_data.settings.ui_scale();
+    for (mut transform, tracker) in time_display_query.iter_mut() {
+        if save_data.is_changed() || tracker.is_added() {
+            transform.scale = Vec3::new(ui_scale, ui_scale, 1.0);
+        }
+    }
+}
 
-    let end_x = rng.gen_range(
-        (start_position.x - MAX_VIBRATE_TRANSLATION)..(start_position.x + MAX_VIBRATE_TRANSLATION),
-    );
-    let end_y = rng.gen_range(
-        (start_position.y - MAX_VIBRATE_TRANSLATION)..(start_position.y + MAX_VIBRATE_TRANSLATION),
-    );
-    let end_position = Vec3::new(end_x, end_y, start_position.z);
-    let position_tween = Tween::new(
-        EaseFunction::SineInOut,
-        TweeningType::Once,
-        VIBRATE_TIME,
-        TransformPositionLens {
-            start: start_position,
-            end: end_position,
-        },
-    )
-    .with_completed_event(true, VIBRATE_TWEEN_COMPLETED);
+/// The daylight tint for the provided time
+fn daylight_tint_color(time: &GameTime) -> Color {
+    let progress = (time.minutes_since_midnight() - DAWN_MINUTES) as f32
+        / (MIDDAY_MINUTES - DAWN_MINUTES) as f32;
+    let dawn: Vec4 = DAWN_TINT.into();
+    let midday: Vec4 = MIDDAY_TINT.into();
+    dawn.lerp(midday, progress.clamp(0.0, 1.0)).into()
+}
 
-    let end_rotation = rng.gen_range(
-        (start_rotation.z - MAX_VIBRATE_ROTATION)..(start_rotation.z + MAX_VIBRATE_ROTATION),
-    );
-    let rotation_tween = Tween::new(
-        EaseFunction::SineInOut,
-        TweeningType::Once,
-        VIBRATE_TIME,
-        TransformRotationLens {
-            start: start_rotation,
-            end: Quat::from_rotation_z(end_rotation),
-        },
-    )
-    .with_completed_event(true, VIBRATE_TWEEN_COMPLETED);
+/// Sets up the tint that shows the morning going by, unless the player has turned off extra effects
+fn daylight_tint_setup(mut commands: Commands, time: Res<GameTime>, save_data: Res<SaveData>) {
+    if save_data.settings.reduce_motion {
+        return;
+    }
 
     commands
-        .entity(entity)
-        .insert(Animator::new(Tracks::new(vec![
-            position_tween,
-            rotation_tween,
-        ])));
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: daylight_tint_color(&time),
+                custom_size: Some(Vec2::new(DAYLIGHT_TINT_SIZE, DAYLIGHT_TINT_SIZE)),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, DAYLIGHT_TINT_Z),
+            ..Default::default()
+        })
+        .insert(GameComponent)
+        .insert(DaylightTint);
 }
 
-/// Handles checking to make sure the phone is still on the table
-fn table_bounds_system(
-    mut commands: Commands,
-    time: Res<GameTime>,
-    num_snoozes: Res<NumSnoozes>,
-    audio: Res<Audio>,
-    phone_query: Query<(Entity, &GlobalTransform), With<Phone>>,
-    mut input_allowed: ResMut<InputAllowed>,
-    mut alarm_active: ResMut<AlarmActive>,
-    asset_server: Res<AssetServer>,
+/// Handles gradually shifting the daylight tint towards the one for the current time
+fn daylight_tint_system(
+    time: Res<Time>,
+    game_time: Res<GameTime>,
+    mut tint_query: Query<&mut Sprite, With<DaylightTint>>,
 ) {
-    for (entity, transform) in phone_query.iter() {
-        if transform.translation.x < TABLE_EDGE_LEFT
-            || transform.translation.x > TABLE_EDGE_RIGHT
-            || transform.translation.y > TABLE_EDGE_TOP
-            || transform.translation.y < TABLE_EDGE_BOTTOM
-        {
-            // it fell off
-            input_allowed.0 = false;
-            alarm_active.0 = false;
-            commands.entity(entity).despawn_recursive();
-            audio.play(asset_server.load(DROP_SOUND));
-            show_game_over_screen(&mut commands, time, num_snoozes, asset_server);
-            return;
+    let target: Vec4 = daylight_tint_color(&game_time).into();
+    let blend = (time.delta_seconds() * DAYLIGHT_TINT_CHANGE_SPEED).min(1.0);
+    for mut sprite in tint_query.iter_mut() {
+        let current: Vec4 = sprite.color.into();
+        sprite.color = current.lerp(target, blend).into();
+    }
+}
+
+/// Handles pulsing the phone's screen while the alarm is going off, unless the player has turned off extra effects
+fn screen_glow_system(
+    time: Res<Time>,
+    alarm_active: Res<AlarmActive>,
+    save_data: Res<SaveData>,
+    mut glow_query: Query<(&mut Sprite, &mut ScreenGlow)>,
+) {
+    let pulsing = alarm_active.0 && !save_data.settings.reduce_motion;
+    for (mut sprite, mut glow) in glow_query.iter_mut() {
+        if !pulsing {
+            glow.phase = 0.0;
+            sprite.color = Color::NONE;
+            continue;
         }
+
+        glow.phase = (glow.phase + time.delta_seconds() / SCREEN_GLOW_PULSE_SECONDS).fract();
+        // bright at the start of each pulse, fading out until the next one
+        let brightness = 1.0 - glow.phase;
+        sprite.color = with_alpha(
+            SCREEN_GLOW_COLOR,
+            SCREEN_GLOW_MAX_ALPHA * brightness * brightness,
+        );
     }
 }
 
-fn show_game_over_screen(
-    commands: &mut Commands,
-    time: Res<GameTime>,
-    num_snoozes: Res<NumSnoozes>,
-    asset_server: Res<AssetServer>,
+/// How tense things should be at the provided time. Once the wake goal has been reached, the tension is over.
+fn target_tension(time: &GameTime, wake_goal: &WakeGoal) -> f32 {
+    let minutes_left = wake_goal.0.minutes_past(time);
+    if minutes_left <= 0 {
+        return 0.0;
+    }
+
+    (1.0 - minutes_left as f32 / TENSION_START_MINUTES).clamp(0.0, 1.0)
+}
+
+/// Handles ramping the tension up or down towards how close the wake goal is
+fn tension_system(
+    time: Res<Time>,
+    game_time: Res<GameTime>,
+    wake_goal: Res<WakeGoal>,
+    mut tension: ResMut<Tension>,
 ) {
-    let text = format!(
-        "Your phone fell on the floor!\nYou got out of bed at {} after hitting snooze {} times",
-        *time, num_snoozes.0
-    );
+    let target = target_tension(&game_time, &wake_goal);
+    if tension.0 == target {
+        return;
+    }
+
+    let step = time.delta_seconds() * TENSION_CHANGE_SPEED;
+    tension.0 = if tension.0 < target {
+        (tension.0 + step).min(target)
+    } else {
+        (tension.0 - step).max(target)
+    };
+}
 
+/// Sets up the tint that gets redder as the wake goal gets closer
+fn tension_tint_setup(mut commands: Commands) {
     commands
-        .spawn_bundle(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(80.0), Val::Percent(20.0)),
-                position_type: PositionType::Absolute,
-                position: Rect {
-                    top: Val::Percent(40.0),
-                    left: Val::Percent(10.0),
-                    ..Default::default()
-                },
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::FlexEnd,
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::NONE,
+                custom_size: Some(Vec2::new(DAYLIGHT_TINT_SIZE, DAYLIGHT_TINT_SIZE)),
                 ..Default::default()
             },
-            color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.7)),
+            transform: Transform::from_xyz(0.0, 0.0, TENSION_TINT_Z),
             ..Default::default()
         })
         .insert(GameComponent)
-        .with_children(|parent| {
-            parent.spawn_bundle(TextBundle {
-                text: Text {
-                    sections: vec![TextSection {
-                        value: text,
-                        style: TextStyle {
-                            font: asset_server.load(MAIN_FONT),
-                            font_size: 30.0,
-                            color: Color::WHITE,
-                        },
-                    }],
-                    alignment: TextAlignment {
-                        horizontal: HorizontalAlign::Center,
-                        vertical: VerticalAlign::Center,
-                    },
-                },
-                style: Style {
-                    align_self: AlignSelf::Center,
-                    ..Default::default()
-                },
-                ..Default::default()
-            });
-        });
+        .insert(TensionTint);
 }
 
-fn alarm_sound_system(
-    audio: Res<Audio>,
-    asset_server: Res<AssetServer>,
-    alarm_active: Res<AlarmActive>,
+/// Handles keeping the tension tint in line with the tension
+fn tension_tint_system(
+    tension: Res<Tension>,
+    mut tint_query: Query<&mut Sprite, With<TensionTint>>,
 ) {
-    if alarm_active.is_changed() {
-        if alarm_active.0 {
-            audio.play_looped_in_channel(
-                asset_server.load(ALARM_SOUND),
-                &AudioChannel::new(ALARM_CHANNEL.to_string()),
-            );
-        } else {
-            //TODO this doesn't seem to do anything
-            audio.stop_channel(&AudioChannel::new(ALARM_CHANNEL.to_string()));
-        }
+    if !tension.is_changed() {
+        return;
+    }
+
+    for mut sprite in tint_query.iter_mut() {
+        sprite.color = with_alpha(TENSION_TINT_COLOR, tension.0 * TENSION_TINT_MAX_ALPHA);
     }
 }
@@ -0,0 +1,297 @@
+use bevy::input::mouse::MouseWheel;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// The most runs that get kept in the history. Older ones get dropped to make room for new ones.
+pub const MAX_HISTORY_LENGTH: usize = 100;
+
+/// How many runs are shown on the history screen at once
+const HISTORY_PAGE_SIZE: usize = 10;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HistoryScroll(0))
+            .add_system_set(SystemSet::on_enter(GameState::History).with_system(history_setup))
+            .add_system_set(
+                SystemSet::on_update(GameState::History).with_system(history_scroll_system),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::History)
+                    .with_system(despawn_components_system::<HistoryComponent>),
+            )
+            .add_system(history_back_button_system);
+    }
+}
+
+#[derive(Component)]
+struct HistoryComponent;
+
+#[derive(Component)]
+struct HistoryBackButton;
+
+#[derive(Component)]
+struct HistoryText;
+
+/// How many runs down the history screen is scrolled
+struct HistoryScroll(usize);
+
+/// How a single past run went
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RunRecord {
+    pub time: GameTime,
+    pub snoozes: u32,
+    pub mode: GameMode,
+    pub difficulty: Difficulty,
+    pub reason: GameOverReason,
+    /// When the run ended, in seconds since the Unix epoch, if it's known
+    pub ended_at: Option<u64>,
+}
+
+impl RunRecord {
+    /// Makes a record of a run that just ended
+    pub fn new(result: &GameResult) -> RunRecord {
+        RunRecord {
+            time: result.final_time,
+            snoozes: result.snoozes,
+            mode: result.mode,
+            difficulty: result.difficulty,
+            reason: result.reason,
+            ended_at: unix_time_now(),
+        }
+    }
+
+    /// The line for this run on the history screen
+    fn text(&self, localization: &Localization) -> String {
+        let date = match self.ended_at {
+            Some(seconds) => format_date(seconds),
+            None => "----------".to_string(),
+        };
+        let outcome_key = match self.reason {
+            GameOverReason::PhoneFell => "history.phone_fell",
+            GameOverReason::TooManyMisses => "history.too_many_misses",
+            GameOverReason::Panicked => "history.panicked",
+            GameOverReason::GotUp => "history.got_up",
+            GameOverReason::FellAsleep => "history.fell_asleep",
+            GameOverReason::Overslept => "history.overslept",
+            GameOverReason::FinallyUp => "history.finally_up",
+            GameOverReason::LateForWork => "history.late_for_work",
+        };
+        localization.localize_with(
+            "history.run",
+            &[
+                ("date", date),
+                (
+                    "mode",
+                    localization.localize(self.mode.name_key()).to_string(),
+                ),
+                (
+                    "difficulty",
+                    localization
+                        .localize(self.difficulty.name_key())
+                        .to_string(),
+                ),
+                ("snoozes", self.snoozes.to_string()),
+                ("time", self.time.to_string()),
+                ("outcome", localization.localize(outcome_key).to_string()),
+            ],
+        )
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn unix_time_now() -> Option<u64> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn unix_time_now() -> Option<u64> {
+    // the system clock isn't available on the web
+    None
+}
+
+/// Formats a number of seconds since the Unix epoch as a UTC date like "2022-04-02"
+fn format_date(unix_seconds: u64) -> String {
+    // from Howard Hinnant's days-to-civil algorithm
+    let days = (unix_seconds / SECONDS_PER_DAY) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// The text for the page of history starting at the provided number of runs in
+fn history_text(save_data: &SaveData, scroll: usize, localization: &Localization) -> String {
+    if save_data.history.is_empty() {
+        return localization.localize("history.empty").to_string();
+    }
+
+    let mut lines = save_data
+        .history
+        .iter()
+        .skip(scroll)
+        .take(HISTORY_PAGE_SIZE)
+        .map(|record| record.text(localization))
+        .collect::<Vec<String>>();
+    lines.push(String::new());
+    lines.push(
+        localization.localize_with(
+            "history.page",
+            &[
+                ("first", (scroll + 1).to_string()),
+                (
+                    "last",
+                    (scroll + HISTORY_PAGE_SIZE)
+                        .min(save_data.history.len())
+                        .to_string(),
+                ),
+                ("total", save_data.history.len().to_string()),
+            ],
+        ),
+    );
+    lines.join("\n")
+}
+
+/// Sets up the history screen.
+fn history_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+    mut history_scroll: ResMut<HistoryScroll>,
+) {
+    let font = asset_server.load(MAIN_FONT);
+    let ui_scale = save_data.settings.ui_scale();
+    history_scroll.0 = 0;
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .insert(HistoryComponent)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    localization.localize("history.title"),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 70.0 * ui_scale,
+                        color: Color::WHITE,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                style: Style {
+                    margin: Rect::all(Val::Px(15.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        history_text(&save_data, history_scroll.0, &localization),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 20.0 * ui_scale,
+                            color: Color::WHITE,
+                        },
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Left,
+                            ..Default::default()
+                        },
+                    ),
+                    style: Style {
+                        margin: Rect::all(Val::Px(15.0)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(HistoryText);
+
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("common.back"),
+                HistoryBackButton,
+            );
+        });
+}
+
+/// Handles scrolling through the history with the mouse wheel or arrow keys.
+fn history_scroll_system(
+    keyboard: Res<Input<KeyCode>>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+    mut history_scroll: ResMut<HistoryScroll>,
+    mut wheel_events: EventReader<MouseWheel>,
+    mut text_query: Query<&mut Text, With<HistoryText>>,
+) {
+    let mut delta: i64 = 0;
+    for event in wheel_events.iter() {
+        // scrolling up moves towards the newest runs
+        delta -= event.y.signum() as i64;
+    }
+    if keyboard.just_pressed(KeyCode::Down) {
+        delta += 1;
+    }
+    if keyboard.just_pressed(KeyCode::Up) {
+        delta -= 1;
+    }
+    if delta == 0 {
+        return;
+    }
+
+    let max_scroll = save_data.history.len().saturating_sub(HISTORY_PAGE_SIZE) as i64;
+    let new_scroll = (history_scroll.0 as i64 + delta).clamp(0, max_scroll) as usize;
+    if new_scroll == history_scroll.0 {
+        return;
+    }
+    history_scroll.0 = new_scroll;
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = history_text(&save_data, history_scroll.0, &localization);
+    }
+}
+
+/// Handles interactions with the back button.
+fn history_back_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<HistoryBackButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Menu).unwrap();
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+const ENGLISH_STRINGS: &str = include_str!("../assets/lang/en.ron");
+const SPANISH_STRINGS: &str = include_str!("../assets/lang/es.ron");
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        let language = app
+            .world
+            .get_resource::<SaveData>()
+            .map(|save_data| save_data.settings.language)
+            .unwrap_or_default();
+
+        app.insert_resource(Localization::new(language))
+            .add_system(language_system);
+    }
+}
+
+/// The language to show text in
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Language {
+    English,
+    Spanish,
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Language::English
+    }
+}
+
+impl Language {
+    /// The language after this one, for cycling through them
+    pub fn next(&self) -> Language {
+        match self {
+            Language::English => Language::Spanish,
+            Language::Spanish => Language::English,
+        }
+    }
+
+    /// The name of this language, written in the language itself so it can be found no matter which one is showing
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+
+    /// The string table for this language, in RON
+    fn strings(&self) -> &'static str {
+        match self {
+            Language::English => ENGLISH_STRINGS,
+            Language::Spanish => SPANISH_STRINGS,
+        }
+    }
+}
+
+/// All the text for the current language, keyed by string ID
+pub struct Localization {
+    language: Language,
+    strings: HashMap<String, String>,
+}
+
+impl Localization {
+    pub fn new(language: Language) -> Localization {
+        let strings = ron::de::from_str(language.strings()).unwrap_or_else(|e| {
+            warn!("couldn't parse strings for {language:?}: {e}");
+            HashMap::new()
+        });
+
+        Localization { language, strings }
+    }
+
+    /// Looks up the text for the provided key, falling back to the key itself if there isn't any
+    pub fn localize<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings.get(key).map(String::as_str).unwrap_or(key)
+    }
+
+    /// Looks up the text for the provided key and fills in its `{name}` placeholders with the provided values
+    pub fn localize_with(&self, key: &str, args: &[(&str, String)]) -> String {
+        args.iter()
+            .fold(self.localize(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), value)
+            })
+    }
+}
+
+/// Handles switching the string table when the language setting changes
+fn language_system(save_data: Res<SaveData>, mut localization: ResMut<Localization>) {
+    if save_data.is_changed() && save_data.settings.language != localization.language {
+        *localization = Localization::new(save_data.settings.language);
+    }
+}
@@ -1,6 +1,8 @@
 use bevy::{
-    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
+    log::LogSettings,
     prelude::*,
+    window::{WindowId, WindowResized},
 };
 use bevy_asset_loader::{AssetCollection, AssetLoader};
 use bevy_inspector_egui::{WorldInspectorParams, WorldInspectorPlugin};
@@ -13,13 +15,68 @@ use menu::*;
 mod game;
 use game::*;
 
-mod game_over;
-use game_over::*;
+mod save;
+use save::*;
+
+mod difficulty;
+use difficulty::*;
+
+mod records;
+use records::*;
+
+mod mode;
+use mode::*;
+
+mod pause;
+use pause::*;
+
+mod scene;
+use scene::*;
+
+mod alarm_volume;
+use alarm_volume::*;
+
+mod stats;
+use stats::*;
+
+mod dynamic_difficulty;
+use dynamic_difficulty::*;
+
+mod localization;
+use localization::*;
+
+mod actions;
+use actions::*;
+
+mod history;
+use history::*;
+
+mod music;
+use music::*;
+
+mod menu_navigation;
+use menu_navigation::*;
+
+mod unlocks;
+use unlocks::*;
+
+mod settings;
+use settings::*;
+
+mod controls;
+use controls::*;
 
 const DEV_MODE: bool = false;
 
+/// How often frame time diagnostics get logged in dev mode, when logging them is turned on
+const DIAGNOSTICS_LOG_INTERVAL_SECONDS: f32 = 5.0;
+
 const MAIN_FONT: &str = "fonts/FiraMono-Medium.ttf";
 
+/// The size of the area the game scene is laid out in. The camera zooms so all of it is always visible, whatever size the window is.
+const SCENE_WIDTH: f32 = 1280.0;
+const SCENE_HEIGHT: f32 = 720.0;
+
 const NORMAL_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
 const HOVERED_BUTTON: Color = Color::rgb(0.35, 0.35, 0.35);
 const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
@@ -29,9 +86,17 @@ pub enum GameState {
     Menu,
     GameLoading,
     Game,
-    GameOver,
+    Records,
+    Stats,
+    History,
+    Unlocks,
+    Settings,
+    Controls,
 }
 
+#[derive(Component)]
+struct MainCamera;
+
 #[derive(AssetCollection)]
 struct FontAssets {
     #[asset(path = "fonts/FiraMono-Medium.ttf")]
@@ -54,10 +119,52 @@ fn despawn_components<T: Component>(to_despawn: Query<Entity, With<T>>, commands
 
 fn setup(mut commands: Commands) {
     // cameras
-    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    commands
+        .spawn_bundle(OrthographicCameraBundle::new_2d())
+        .insert(MainCamera);
     commands.spawn_bundle(UiCameraBundle::default());
 }
 
+/// Spawns a standard button with a text label and the provided marker component
+fn spawn_button<T: Component>(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    ui_scale: f32,
+    label: &str,
+    marker: T,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(250.0 * ui_scale), Val::Px(100.0 * ui_scale)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                margin: Rect::all(Val::Px(15.0 * ui_scale)),
+                ..Default::default()
+            },
+            color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .insert(marker)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    label,
+                    TextStyle {
+                        font,
+                        font_size: 40.0 * ui_scale,
+                        color: Color::SEA_GREEN,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                ..Default::default()
+            });
+        });
+}
+
 type InteractedButtonTuple = (Changed<Interaction>, With<Button>);
 
 /// Handles changing button colors when they're interacted with.
@@ -73,6 +180,56 @@ fn button_color_system(
     }
 }
 
+/// Handles hiding the cursor during gameplay, and showing it everywhere else.
+fn cursor_visibility_system(
+    game_state: Res<State<GameState>>,
+    paused: Res<Paused>,
+    save_data: Res<SaveData>,
+    mut windows: ResMut<Windows>,
+) {
+    let visible = *game_state.current() != GameState::Game
+        || paused.is_paused()
+        || save_data.settings.show_cursor_in_game
+        || save_data.settings.mouse_control;
+
+    if let Some(window) = windows.get_primary_mut() {
+        if window.cursor_visible() != visible {
+            window.set_cursor_visibility(visible);
+        }
+    }
+}
+
+/// Handles zooming the camera so the whole scene fits in the window, whatever size or shape the window is
+fn camera_fit_system(
+    windows: Res<Windows>,
+    mut resize_events: EventReader<WindowResized>,
+    mut camera_query: Query<&mut OrthographicProjection, With<MainCamera>>,
+    mut fitted: Local<bool>,
+) {
+    let resized = resize_events
+        .iter()
+        .any(|event| event.id == WindowId::primary());
+    if *fitted && !resized {
+        return;
+    }
+
+    let window = match windows.get_primary() {
+        Some(window) => window,
+        None => return,
+    };
+    if window.width() <= 0.0 || window.height() <= 0.0 {
+        // minimized, so there's nothing to fit
+        return;
+    }
+
+    // zoom out on whichever side is too short for the scene, leaving extra room on the other one
+    let scale = (SCENE_WIDTH / window.width()).max(SCENE_HEIGHT / window.height());
+    for mut projection in camera_query.iter_mut() {
+        projection.scale = scale;
+        *fitted = true;
+    }
+}
+
 /// Handles showing the world inspector.
 fn world_inspector_system(
     keyboard: Res<Input<KeyCode>>,
@@ -83,32 +240,121 @@ fn world_inspector_system(
     }
 }
 
+/// Whether frame time diagnostics are being periodically logged
+struct DiagnosticsLog {
+    enabled: bool,
+    timer: Timer,
+}
+
+/// Handles turning diagnostics logging on and off.
+fn diagnostics_log_toggle_system(
+    keyboard: Res<Input<KeyCode>>,
+    mut diagnostics_log: ResMut<DiagnosticsLog>,
+) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        diagnostics_log.enabled = !diagnostics_log.enabled;
+        diagnostics_log.timer.reset();
+    }
+}
+
+/// Handles periodically logging frame time diagnostics, if that's turned on.
+fn diagnostics_log_system(
+    time: Res<Time>,
+    diagnostics: Res<Diagnostics>,
+    mut diagnostics_log: ResMut<DiagnosticsLog>,
+) {
+    if !diagnostics_log.enabled || !diagnostics_log.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    for id in [
+        FrameTimeDiagnosticsPlugin::FPS,
+        FrameTimeDiagnosticsPlugin::FRAME_TIME,
+    ] {
+        if let Some(diagnostic) = diagnostics.get(id) {
+            if let Some(average) = diagnostic.average() {
+                info!("{}: {average:.2}", diagnostic.name);
+            }
+        }
+    }
+}
+
+/// Whether there's an output device to play audio through, which there might not be on headless or minimal systems
+#[cfg(not(target_arch = "wasm32"))]
+fn audio_output_available() -> bool {
+    use cpal::traits::HostTrait;
+    cpal::default_host().default_output_device().is_some()
+}
+
+/// Browsers deal with output devices themselves
+#[cfg(target_arch = "wasm32")]
+fn audio_output_available() -> bool {
+    true
+}
+
 fn main() {
     let mut app = App::new();
+    let audio_available = audio_output_available();
+    if !audio_available {
+        // the audio plugin logs its own errors about the missing device, so those get silenced in favor of the one warning below
+        app.insert_resource(LogSettings {
+            filter: format!(
+                "{},bevy_kira_audio=off,kira=off",
+                LogSettings::default().filter
+            ),
+            ..Default::default()
+        });
+    }
     AssetLoader::new(GameState::Menu)
         .with_collection::<FontAssets>()
         .build(&mut app);
     app.insert_resource(ClearColor(Color::BLACK))
         .insert_resource(WindowDescriptor {
             title: "Snooze".to_string(),
-            width: 1280.0,
-            height: 720.0,
+            width: SCENE_WIDTH,
+            height: SCENE_HEIGHT,
             ..Default::default()
         })
+        .insert_resource(Difficulty::default())
+        .insert_resource(GameMode::default())
         .add_state(GameState::Menu)
         .add_startup_system(setup)
         .add_plugin(MenuPlugin)
         .add_plugin(GamePlugin)
-        .add_plugin(GameOverPlugin)
+        .add_plugin(SavePlugin)
+        .add_plugin(LocalizationPlugin)
+        .add_plugin(RecordsPlugin)
+        .add_plugin(StatsPlugin)
+        .add_plugin(HistoryPlugin)
+        .add_plugin(UnlocksPlugin)
+        .add_plugin(SettingsPlugin)
+        .add_plugin(ControlsPlugin)
+        .add_plugin(PausePlugin)
+        .add_plugin(SceneConfigPlugin)
+        .add_plugin(AlarmVolumePlugin)
+        .add_plugin(MusicPlugin)
+        .add_plugin(MenuNavigationPlugin)
+        .add_plugin(DynamicDifficultyPlugin)
+        .add_plugin(ActionPlugin)
         .add_system(button_color_system)
-        .add_plugins(DefaultPlugins)
-        .add_plugin(AudioPlugin)
-        .add_plugin(TweeningPlugin);
+        .add_system(cursor_visibility_system)
+        .add_system(camera_fit_system)
+        .add_plugins(DefaultPlugins);
+
+    if !audio_available {
+        warn!("no audio output device found, so the game will run without sound");
+    }
+    // the game still runs the same without a device, since nothing it does depends on sounds actually playing
+    app.add_plugin(AudioPlugin).add_plugin(TweeningPlugin);
 
     if DEV_MODE {
-        app.add_system(bevy::input::system::exit_on_esc_system)
-            .add_system(world_inspector_system)
-            .add_plugin(LogDiagnosticsPlugin::default())
+        app.add_system(world_inspector_system)
+            .add_system(diagnostics_log_toggle_system)
+            .add_system(diagnostics_log_system)
+            .insert_resource(DiagnosticsLog {
+                enabled: false,
+                timer: Timer::from_seconds(DIAGNOSTICS_LOG_INTERVAL_SECONDS, true),
+            })
             .add_plugin(FrameTimeDiagnosticsPlugin::default())
             .add_plugin(WorldInspectorPlugin::new())
             .insert_resource(WorldInspectorParams {
@@ -4,12 +4,21 @@ pub struct MenuPlugin;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_set(SystemSet::on_enter(GameState::Menu).with_system(menu_setup))
+        app.insert_resource(SeedInput(String::new()))
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(menu_setup))
             .add_system_set(
                 SystemSet::on_exit(GameState::Menu)
                     .with_system(despawn_components_system::<MenuComponent>),
             )
-            .add_system(start_button_system);
+            .add_system(start_button_system)
+            .add_system(records_button_system)
+            .add_system(stats_button_system)
+            .add_system(history_button_system)
+            .add_system(unlocks_button_system)
+            .add_system(settings_button_system)
+            .add_system(mode_button_system)
+            .add_system(difficulty_button_system)
+            .add_system_set(SystemSet::on_update(GameState::Menu).with_system(seed_input_system));
     }
 }
 
@@ -19,10 +28,112 @@ struct MenuComponent;
 #[derive(Component)]
 struct StartButton;
 
+#[derive(Component)]
+struct RecordsButton;
+
+#[derive(Component)]
+struct StatsButton;
+
+#[derive(Component)]
+struct HistoryButton;
+
+#[derive(Component)]
+struct UnlocksButton;
+
+#[derive(Component)]
+struct SettingsButton;
+
+#[derive(Component)]
+struct ModeButton;
+
+/// A button for picking the difficulty for the next run
+#[derive(Component)]
+struct DifficultyButton(Difficulty);
+
+#[derive(Component)]
+struct HighScoreText;
+
+#[derive(Component)]
+struct SeedText;
+
+/// The longest a typed-in seed can be. Any more digits than this wouldn't fit in a `u64` anyway.
+const MAX_SEED_DIGITS: usize = 20;
+
+/// The seed the player has typed in on the menu, if any
+pub struct SeedInput(pub String);
+
+impl SeedInput {
+    /// The seed to use for the next run, or `None` if one should be picked at random
+    pub fn seed(&self) -> Option<u64> {
+        self.0.parse().ok()
+    }
+}
+
+fn seed_text(seed_input: &SeedInput, localization: &Localization) -> String {
+    if seed_input.0.is_empty() {
+        localization.localize("menu.seed_random").to_string()
+    } else if seed_input.seed().is_none() {
+        localization.localize_with("menu.seed_invalid", &[("seed", seed_input.0.clone())])
+    } else {
+        localization.localize_with("menu.seed", &[("seed", seed_input.0.clone())])
+    }
+}
+
+/// The label for the button for the provided difficulty
+fn difficulty_label(
+    difficulty: Difficulty,
+    selected: Difficulty,
+    localization: &Localization,
+) -> String {
+    let name = localization.localize(difficulty.name_key());
+    if difficulty == selected {
+        format!("> {name} <")
+    } else {
+        name.to_string()
+    }
+}
+
+/// The text showing the best run for the provided difficulty
+fn high_score_text(
+    save_data: &SaveData,
+    difficulty: Difficulty,
+    localization: &Localization,
+) -> String {
+    match save_data.high_scores.get(&difficulty) {
+        Some(score) => localization.localize_with(
+            "menu.best",
+            &[
+                (
+                    "difficulty",
+                    localization.localize(difficulty.name_key()).to_string(),
+                ),
+                ("snoozes", score.snoozes.to_string()),
+                ("time", score.time.to_string()),
+            ],
+        ),
+        None => localization.localize_with(
+            "menu.best_none",
+            &[(
+                "difficulty",
+                localization.localize(difficulty.name_key()).to_string(),
+            )],
+        ),
+    }
+}
+
 /// Sets up the main menu screen.
-fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn menu_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    save_data: Res<SaveData>,
+    difficulty: Res<Difficulty>,
+    game_mode: Res<GameMode>,
+    seed_input: Res<SeedInput>,
+    localization: Res<Localization>,
+) {
     // title text
     let font = asset_server.load(MAIN_FONT);
+    let ui_scale = save_data.settings.ui_scale();
     commands
         .spawn_bundle(NodeBundle {
             style: Style {
@@ -44,10 +155,10 @@ fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             parent.spawn_bundle(TextBundle {
                 text: Text {
                     sections: vec![TextSection {
-                        value: "Snooze".to_string(),
+                        value: localization.localize("menu.title").to_string(),
                         style: TextStyle {
                             font: font.clone(),
-                            font_size: 70.0,
+                            font_size: 70.0 * ui_scale,
                             color: Color::WHITE,
                         },
                     }],
@@ -64,18 +175,18 @@ fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
             });
         });
 
-    // start button
+    // high score
     commands
         .spawn_bundle(NodeBundle {
             style: Style {
-                size: Size::new(Val::Percent(100.0), Val::Percent(50.0)),
+                size: Size::new(Val::Percent(100.0), Val::Px(50.0)),
                 position_type: PositionType::Absolute,
                 position: Rect {
-                    bottom: Val::Px(0.0),
+                    top: Val::Percent(55.0),
                     ..Default::default()
                 },
                 justify_content: JustifyContent::Center,
-                align_items: AlignItems::FlexEnd,
+                align_items: AlignItems::Center,
                 ..Default::default()
             },
             color: UiColor(Color::NONE),
@@ -84,42 +195,187 @@ fn menu_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
         .insert(MenuComponent)
         .with_children(|parent| {
             parent
-                .spawn_bundle(ButtonBundle {
-                    style: Style {
-                        size: Size::new(Val::Px(250.0), Val::Px(100.0)),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        margin: Rect::all(Val::Px(15.0)),
-                        ..Default::default()
-                    },
-                    color: NORMAL_BUTTON.into(),
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        high_score_text(&save_data, *difficulty, &localization),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 25.0 * ui_scale,
+                            color: Color::WHITE,
+                        },
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        },
+                    ),
                     ..Default::default()
                 })
-                .insert(StartButton)
-                .with_children(|parent| {
-                    parent.spawn_bundle(TextBundle {
-                        text: Text::with_section(
-                            "Go to sleep",
-                            TextStyle {
-                                font: font.clone(),
-                                font_size: 40.0,
-                                color: Color::SEA_GREEN,
-                            },
-                            TextAlignment {
-                                horizontal: HorizontalAlign::Center,
-                                ..Default::default()
-                            },
-                        ),
+                .insert(HighScoreText);
+        });
+
+    // rage quit message
+    if save_data.is_rage_quitter() {
+        commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Px(50.0)),
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        top: Val::Percent(62.0),
                         ..Default::default()
-                    });
+                    },
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                color: UiColor(Color::NONE),
+                ..Default::default()
+            })
+            .insert(MenuComponent)
+            .with_children(|parent| {
+                parent.spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        localization.localize("menu.rage_quit"),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 25.0 * ui_scale,
+                            color: Color::GRAY,
+                        },
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        },
+                    ),
+                    ..Default::default()
                 });
+            });
+    }
+
+    // seed
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(50.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Percent(69.0),
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .insert(MenuComponent)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        seed_text(&seed_input, &localization),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 20.0 * ui_scale,
+                            color: Color::GRAY,
+                        },
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        },
+                    ),
+                    ..Default::default()
+                })
+                .insert(SeedText);
+        });
+
+    // start button
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(50.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    bottom: Val::Px(0.0),
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::FlexEnd,
+                // big buttons go onto another row rather than off the screen
+                flex_wrap: FlexWrap::WrapReverse,
+                align_content: AlignContent::FlexStart,
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .insert(MenuComponent)
+        .with_children(|parent| {
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("menu.start"),
+                StartButton,
+            );
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize(game_mode.name_key()),
+                ModeButton,
+            );
+            for option in Difficulty::ALL {
+                spawn_button(
+                    parent,
+                    font.clone(),
+                    ui_scale,
+                    &difficulty_label(option, *difficulty, &localization),
+                    DifficultyButton(option),
+                );
+            }
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("menu.records"),
+                RecordsButton,
+            );
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("menu.stats"),
+                StatsButton,
+            );
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("menu.history"),
+                HistoryButton,
+            );
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("menu.unlocks"),
+                UnlocksButton,
+            );
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("menu.settings"),
+                SettingsButton,
+            );
         });
 }
 
 /// Handles interactions with the start button.
 fn start_button_system(
     mut game_state: ResMut<State<GameState>>,
-    interaction_query: Query<&Interaction, Changed<Interaction>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<StartButton>)>,
 ) {
     for interaction in interaction_query.iter() {
         if *interaction == Interaction::Clicked {
@@ -127,3 +383,142 @@ fn start_button_system(
         }
     }
 }
+
+/// Handles interactions with the records button.
+fn records_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<RecordsButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Records).unwrap();
+        }
+    }
+}
+
+/// Handles interactions with the stats button.
+fn stats_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<StatsButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Stats).unwrap();
+        }
+    }
+}
+
+/// Handles interactions with the history button.
+fn history_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<HistoryButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::History).unwrap();
+        }
+    }
+}
+
+/// Handles interactions with the unlocks button.
+fn unlocks_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<UnlocksButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Unlocks).unwrap();
+        }
+    }
+}
+
+/// Handles interactions with the settings button.
+fn settings_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Settings).unwrap();
+        }
+    }
+}
+
+/// Handles interactions with the game mode button.
+fn mode_button_system(
+    mut game_mode: ResMut<GameMode>,
+    localization: Res<Localization>,
+    interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<ModeButton>)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (interaction, children) in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            *game_mode = game_mode.next();
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(*child) {
+                    text.sections[0].value =
+                        localization.localize(game_mode.name_key()).to_string();
+                }
+            }
+        }
+    }
+}
+
+/// Handles interactions with the difficulty buttons.
+fn difficulty_button_system(
+    mut difficulty: ResMut<Difficulty>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+    interaction_query: Query<(&Interaction, &DifficultyButton), Changed<Interaction>>,
+    button_query: Query<(&DifficultyButton, &Children)>,
+    mut button_text_query: Query<&mut Text, Without<HighScoreText>>,
+    mut high_score_text_query: Query<&mut Text, With<HighScoreText>>,
+) {
+    let mut picked = false;
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            *difficulty = button.0;
+            picked = true;
+        }
+    }
+
+    if !picked {
+        return;
+    }
+
+    for (button, children) in button_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = button_text_query.get_mut(*child) {
+                text.sections[0].value = difficulty_label(button.0, *difficulty, &localization);
+            }
+        }
+    }
+    for mut text in high_score_text_query.iter_mut() {
+        text.sections[0].value = high_score_text(&save_data, *difficulty, &localization);
+    }
+}
+
+/// Handles typing in a seed for the next run.
+fn seed_input_system(
+    keyboard: Res<Input<KeyCode>>,
+    mut seed_input: ResMut<SeedInput>,
+    localization: Res<Localization>,
+    mut char_events: EventReader<ReceivedCharacter>,
+    mut text_query: Query<&mut Text, With<SeedText>>,
+) {
+    for event in char_events.iter() {
+        if event.char.is_ascii_digit() && seed_input.0.len() < MAX_SEED_DIGITS {
+            seed_input.0.push(event.char);
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Back) {
+        seed_input.0.pop();
+    }
+
+    if seed_input.is_changed() {
+        for mut text in text_query.iter_mut() {
+            text.sections[0].value = seed_text(&seed_input, &localization);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::*;
+
+/// The shortest a repeat delay or interval can be, so holding a key can't zip through everything in a single frame
+pub const MIN_REPEAT_SECONDS: f32 = 0.02;
+
+pub struct MenuNavigationPlugin;
+
+impl Plugin for MenuNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MenuFocus::default())
+            .insert_resource(NavigationRepeat::default())
+            .add_system(menu_navigation_system.label("menu_navigation"))
+            .add_system(focus_highlight_system.after("menu_navigation"));
+    }
+}
+
+/// Which way to move the focus through the buttons
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum NavigationDirection {
+    Previous,
+    Next,
+}
+
+/// Which button is focused for keyboard navigation, if any
+#[derive(Default)]
+struct MenuFocus {
+    focused: Option<Entity>,
+    /// The button that was activated with the keyboard last frame, so it can be let go of
+    activated: Option<Entity>,
+}
+
+/// Tracks a navigation key being held down, for repeating the movement like an OS does with held keys
+#[derive(Default)]
+struct NavigationRepeat {
+    held: Option<(NavigationDirection, Timer)>,
+}
+
+/// The direction the provided keys are pointing in, if any
+fn held_direction(keyboard: &Input<KeyCode>) -> Option<NavigationDirection> {
+    if keyboard.pressed(KeyCode::Up) || keyboard.pressed(KeyCode::Left) {
+        Some(NavigationDirection::Previous)
+    } else if keyboard.pressed(KeyCode::Down) || keyboard.pressed(KeyCode::Right) {
+        Some(NavigationDirection::Next)
+    } else {
+        None
+    }
+}
+
+/// Handles moving the focus between buttons with the arrow keys, and pressing the focused one with Enter.
+/// Gameplay uses the arrow keys for the arm, so this doesn't do anything during a run unless it's paused or over.
+fn menu_navigation_system(
+    time: Res<Time>,
+    keyboard: Res<Input<KeyCode>>,
+    game_state: Res<State<GameState>>,
+    paused: Res<Paused>,
+    death_sequence: Res<DeathSequence>,
+    save_data: Res<SaveData>,
+    mut menu_focus: ResMut<MenuFocus>,
+    mut navigation_repeat: ResMut<NavigationRepeat>,
+    mut button_query: Query<(Entity, &GlobalTransform, &mut Interaction), With<Button>>,
+) {
+    // let go of whatever got pressed last frame
+    if let Some(activated) = menu_focus.activated.take() {
+        if let Ok((_, _, mut interaction)) = button_query.get_mut(activated) {
+            *interaction = Interaction::None;
+        }
+    }
+
+    if *game_state.current() == GameState::Game
+        && !paused.is_paused()
+        && !death_sequence.is_started()
+    {
+        navigation_repeat.held = None;
+        return;
+    }
+
+    // the focused button might have been despawned along with its screen
+    if let Some(focused) = menu_focus.focused {
+        if button_query.get(focused).is_err() {
+            menu_focus.focused = None;
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Return) {
+        if let Some(focused) = menu_focus.focused {
+            if let Ok((_, _, mut interaction)) = button_query.get_mut(focused) {
+                *interaction = Interaction::Clicked;
+                menu_focus.activated = Some(focused);
+            }
+        }
+        return;
+    }
+
+    let settings = &save_data.settings;
+    let direction = match held_direction(&keyboard) {
+        Some(direction) => direction,
+        None => {
+            navigation_repeat.held = None;
+            return;
+        }
+    };
+    let should_move = match navigation_repeat.held.as_mut() {
+        Some((held_direction, timer)) if *held_direction == direction => {
+            let repeat = timer.tick(time.delta()).just_finished();
+            if repeat {
+                *timer = Timer::from_seconds(
+                    settings.menu_repeat_interval.max(MIN_REPEAT_SECONDS),
+                    false,
+                );
+            }
+            repeat
+        }
+        _ => {
+            // just started holding it, so move right away and then wait a bit before repeating
+            navigation_repeat.held = Some((
+                direction,
+                Timer::from_seconds(settings.menu_repeat_delay.max(MIN_REPEAT_SECONDS), false),
+            ));
+            true
+        }
+    };
+    if !should_move {
+        return;
+    }
+
+    // go through the buttons in reading order, top to bottom and then left to right
+    let mut buttons = button_query
+        .iter()
+        .map(|(entity, transform, _)| (entity, transform.translation))
+        .collect::<Vec<(Entity, Vec3)>>();
+    if buttons.is_empty() {
+        return;
+    }
+    buttons.sort_by(|(_, a), (_, b)| {
+        b.y.partial_cmp(&a.y)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal))
+    });
+
+    let current_index = menu_focus
+        .focused
+        .and_then(|focused| buttons.iter().position(|(entity, _)| *entity == focused));
+    let new_index = match (current_index, direction) {
+        (None, NavigationDirection::Next) => 0,
+        (None, NavigationDirection::Previous) => buttons.len() - 1,
+        (Some(index), NavigationDirection::Next) => (index + 1) % buttons.len(),
+        (Some(index), NavigationDirection::Previous) => (index + buttons.len() - 1) % buttons.len(),
+    };
+    menu_focus.focused = Some(buttons[new_index].0);
+}
+
+/// Handles showing which button is focused, the same way as one that's hovered over
+fn focus_highlight_system(
+    menu_focus: Res<MenuFocus>,
+    mut previously_focused: Local<Option<Entity>>,
+    mut button_query: Query<(&Interaction, &mut UiColor), With<Button>>,
+) {
+    if !menu_focus.is_changed() || *previously_focused == menu_focus.focused {
+        return;
+    }
+
+    if let Some(entity) = *previously_focused {
+        if let Ok((interaction, mut color)) = button_query.get_mut(entity) {
+            if *interaction == Interaction::None {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+
+    if let Some(entity) = menu_focus.focused {
+        if let Ok((interaction, mut color)) = button_query.get_mut(entity) {
+            if *interaction == Interaction::None {
+                *color = HOVERED_BUTTON.into();
+            }
+        }
+    }
+
+    *previously_focused = menu_focus.focused;
+}
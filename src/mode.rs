@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// When the player has to be up by on the weekend
+const WEEKEND_WAKE_GOAL: GameTime = GameTime {
+    hour: 12,
+    minute: 0,
+};
+
+/// How many snoozes it takes to finally get up on a workday
+const WORKDAY_SNOOZE_GOAL: u32 = 8;
+
+/// What the screen fades to in night owl mode
+const NIGHTMARE_OVERLAY_COLOR: Color = Color::rgb(0.25, 0.0, 0.02);
+
+/// What kind of run to play
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum GameMode {
+    /// Snooze until the phone falls off the table
+    Classic,
+    /// Keep going past the wake goal while the alarm gets more and more aggressive
+    NightOwl,
+    /// Sleep in with a bigger table and no penalty for missing
+    Weekend,
+    /// Snooze a set number of times and then get up, without sleeping past the wake goal
+    Workday,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Classic,
+        GameMode::NightOwl,
+        GameMode::Weekend,
+        GameMode::Workday,
+    ];
+
+    /// The key for the name of this mode in the string table
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "common.mode_classic",
+            GameMode::NightOwl => "common.mode_night_owl",
+            GameMode::Weekend => "common.mode_weekend",
+            GameMode::Workday => "common.mode_workday",
+        }
+    }
+
+    /// The time the player is supposed to get up at in this mode
+    pub fn wake_goal(&self) -> GameTime {
+        match self {
+            GameMode::Classic | GameMode::NightOwl | GameMode::Workday => WAKE_GOAL,
+            GameMode::Weekend => WEEKEND_WAKE_GOAL,
+        }
+    }
+
+    /// The edges of the table in this mode
+    pub fn table_bounds(&self) -> TableBounds {
+        match self {
+            GameMode::Classic | GameMode::NightOwl | GameMode::Workday => TableBounds::default(),
+            GameMode::Weekend => TableBounds {
+                left: -620.0,
+                right: 520.0,
+                top: 400.0,
+                bottom: -340.0,
+            },
+        }
+    }
+
+    /// How many seconds input is locked for after missing the snooze button in this mode, if at all
+    pub fn miss_penalty_seconds(&self) -> Option<f32> {
+        match self {
+            GameMode::Classic | GameMode::NightOwl | GameMode::Workday => {
+                Some(MISS_PENALTY_SECONDS)
+            }
+            GameMode::Weekend => None,
+        }
+    }
+
+    /// Whether the deadline for the difficulty applies in this mode
+    pub fn has_deadline(&self) -> bool {
+        match self {
+            GameMode::Classic | GameMode::NightOwl => true,
+            // no work on the weekend, and workdays have their own wake goal to beat
+            GameMode::Weekend | GameMode::Workday => false,
+        }
+    }
+
+    /// Whether fake notifications pop up on the phone in this mode
+    pub fn has_notifications(&self) -> bool {
+        match self {
+            GameMode::Classic | GameMode::Weekend | GameMode::Workday => false,
+            // the group chat is up bright and early
+            GameMode::NightOwl => true,
+        }
+    }
+
+    /// How the phone moves around when it vibrates in this mode
+    pub fn vibration_pattern(&self) -> VibrationPattern {
+        match self {
+            GameMode::Classic | GameMode::Workday => VibrationPattern::Jitter,
+            // it's trying to get away
+            GameMode::NightOwl => VibrationPattern::Drift,
+            // the bigger table gives the phone room to wander, so reel it back in a bit
+            GameMode::Weekend => VibrationPattern::Pulse,
+        }
+    }
+
+    /// How many minutes each snooze grants in this mode
+    pub fn snooze_reward(&self) -> SnoozeReward {
+        match self {
+            GameMode::Classic | GameMode::NightOwl | GameMode::Workday => SnoozeReward::default(),
+            // a clean streak earns a longer lie-in
+            GameMode::Weekend => SnoozeReward {
+                bonus_minutes_per_streak: 1,
+                max_bonus_minutes: 5,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// The colors used for this mode
+    pub fn theme(&self) -> Theme {
+        match self {
+            GameMode::Classic | GameMode::Weekend | GameMode::Workday => Theme::default(),
+            // it's turning into a nightmare
+            GameMode::NightOwl => Theme {
+                overlay_color: NIGHTMARE_OVERLAY_COLOR,
+                game_over_panel_color: with_alpha(NIGHTMARE_OVERLAY_COLOR, GAME_OVER_PANEL_ALPHA),
+            },
+        }
+    }
+
+    /// How many snoozes it takes to get up and win in this mode, or `None` if there's no winning by snoozing
+    pub fn snooze_goal(&self) -> Option<u32> {
+        match self {
+            GameMode::Classic | GameMode::NightOwl | GameMode::Weekend => None,
+            GameMode::Workday => Some(WORKDAY_SNOOZE_GOAL),
+        }
+    }
+
+    /// The mode after this one, for cycling through them on the menu
+    pub fn next(&self) -> GameMode {
+        let index = GameMode::ALL
+            .iter()
+            .position(|mode| mode == self)
+            .unwrap_or(0);
+        GameMode::ALL[(index + 1) % GameMode::ALL.len()]
+    }
+}
+
+impl Default for GameMode {
+    fn default() -> Self {
+        GameMode::Classic
+    }
+}
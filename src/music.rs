@@ -0,0 +1,90 @@
+use bevy_kira_audio::{Audio, AudioChannel};
+
+use crate::*;
+
+const MENU_MUSIC: &str = "sounds/menu_music.wav";
+pub const MUSIC_CHANNEL: &str = "music";
+
+/// How long it takes the music to fade all the way in or out
+const MUSIC_FADE_SECONDS: f32 = 1.5;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MusicFade::default())
+            .add_system_set(SystemSet::on_enter(GameState::Menu).with_system(music_start_system))
+            .add_system_set(
+                SystemSet::on_enter(GameState::GameLoading).with_system(music_stop_system),
+            )
+            .add_system(music_volume_system);
+    }
+}
+
+/// How loud the menu music is, relative to its full volume, and where it's headed
+#[derive(Default)]
+struct MusicFade {
+    playing: bool,
+    current: f32,
+    target: f32,
+}
+
+/// Handles starting the menu music, or bringing it back up if it was on its way out
+fn music_start_system(
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+    mut music_fade: ResMut<MusicFade>,
+) {
+    if !music_fade.playing {
+        let music_channel = AudioChannel::new(MUSIC_CHANNEL.to_string());
+        audio.set_volume_in_channel(0.0, &music_channel);
+        audio.play_looped_in_channel(asset_server.load(MENU_MUSIC), &music_channel);
+        music_fade.playing = true;
+        music_fade.current = 0.0;
+    }
+
+    music_fade.target = 1.0;
+}
+
+/// Handles fading the menu music out so it's gone before the alarm starts
+fn music_stop_system(mut music_fade: ResMut<MusicFade>) {
+    music_fade.target = 0.0;
+}
+
+/// Handles fading the music towards where it's headed and keeping its volume in line with the settings
+fn music_volume_system(
+    time: Res<Time>,
+    audio: Res<Audio>,
+    save_data: Res<SaveData>,
+    mut music_fade: ResMut<MusicFade>,
+) {
+    if !music_fade.playing {
+        return;
+    }
+
+    let fading = music_fade.current != music_fade.target;
+    if !fading && !save_data.is_changed() {
+        // nothing new to apply
+        return;
+    }
+
+    let step = time.delta_seconds() / MUSIC_FADE_SECONDS;
+    music_fade.current = if music_fade.current < music_fade.target {
+        (music_fade.current + step).min(music_fade.target)
+    } else {
+        (music_fade.current - step).max(music_fade.target)
+    };
+
+    let music_channel = AudioChannel::new(MUSIC_CHANNEL.to_string());
+    let settings = &save_data.settings;
+    audio.set_volume_in_channel(
+        settings.master_volume * settings.music_volume * music_fade.current,
+        &music_channel,
+    );
+
+    if music_fade.current <= 0.0 && music_fade.target <= 0.0 {
+        // all the way out, so stop it for real
+        audio.stop_channel(&music_channel);
+        music_fade.playing = false;
+    }
+}
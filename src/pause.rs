@@ -0,0 +1,250 @@
+use bevy::{ecs::schedule::ShouldRun, window::WindowFocused};
+use bevy_kira_audio::Audio;
+use bevy_tweening::{Animator, AnimatorState};
+
+use crate::*;
+
+const PAUSE_KEY: KeyCode = KeyCode::Escape;
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Paused::default())
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_system(focus_pause_system)
+                    .with_system(pause_key_system.label("pause_key"))
+                    .with_system(pause_menu_system.after("pause_key"))
+                    .with_system(resume_button_system)
+                    .with_system(quit_to_menu_button_system),
+            )
+            .add_system_set(
+                SystemSet::on_exit(GameState::Game)
+                    .with_system(unpause)
+                    .with_system(despawn_components_system::<PauseMenuComponent>),
+            )
+            .add_system(pause_effects_system);
+    }
+}
+
+#[derive(Component)]
+struct PauseMenuComponent;
+
+#[derive(Component)]
+struct ResumeButton;
+
+#[derive(Component)]
+struct QuitToMenuButton;
+
+/// Whether gameplay is paused, and why
+#[derive(Default)]
+pub struct Paused {
+    /// The player paused the game themselves
+    pub by_player: bool,
+    /// The game window lost focus
+    pub by_focus_loss: bool,
+}
+
+impl Paused {
+    pub fn is_paused(&self) -> bool {
+        self.by_player || self.by_focus_loss
+    }
+}
+
+/// Run criteria for systems that should only run while a run is going and isn't paused.
+/// This checks the state itself, since adding it to an `on_update` set replaces the state's own run criteria.
+pub fn game_running(game_state: Res<State<GameState>>, paused: Res<Paused>) -> ShouldRun {
+    if *game_state.current() != GameState::Game || paused.is_paused() {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+/// Handles pausing the game when the window loses focus, and resuming it when it comes back
+fn focus_pause_system(
+    save_data: Res<SaveData>,
+    mut paused: ResMut<Paused>,
+    mut focus_events: EventReader<WindowFocused>,
+) {
+    for event in focus_events.iter() {
+        if event.focused {
+            if paused.by_focus_loss {
+                paused.by_focus_loss = false;
+            }
+        } else if save_data.settings.auto_pause_on_focus_loss {
+            paused.by_focus_loss = true;
+        }
+    }
+}
+
+/// Handles pausing and unpausing the game with the pause key. Once the run is over there's nothing left to pause.
+fn pause_key_system(
+    keyboard: Res<Input<KeyCode>>,
+    death_sequence: Res<DeathSequence>,
+    mut paused: ResMut<Paused>,
+) {
+    if keyboard.just_pressed(PAUSE_KEY) && !death_sequence.is_started() {
+        paused.by_player = !paused.by_player;
+    }
+}
+
+/// Handles showing the pause menu while the player has the game paused, and hiding it once they don't
+fn pause_menu_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+    paused: Res<Paused>,
+    pause_menu_query: Query<Entity, With<PauseMenuComponent>>,
+) {
+    if !paused.is_changed() {
+        return;
+    }
+
+    let showing = pause_menu_query.iter().next().is_some();
+    if paused.by_player && !showing {
+        spawn_pause_menu(&mut commands, &asset_server, &save_data, &localization);
+    } else if !paused.by_player && showing {
+        for entity in pause_menu_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Spawns the pause menu over the game
+fn spawn_pause_menu(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    save_data: &SaveData,
+    localization: &Localization,
+) {
+    let font = asset_server.load(MAIN_FONT);
+    let ui_scale = save_data.settings.ui_scale();
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(Color::rgba(0.0, 0.0, 0.0, 0.7)),
+            ..Default::default()
+        })
+        .insert(PauseMenuComponent)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    localization.localize("pause.title"),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 70.0 * ui_scale,
+                        color: Color::WHITE,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                style: Style {
+                    margin: Rect::all(Val::Px(15.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("pause.resume"),
+                ResumeButton,
+            );
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("pause.quit"),
+                QuitToMenuButton,
+            );
+        });
+}
+
+/// Handles interactions with the resume button.
+fn resume_button_system(
+    mut paused: ResMut<Paused>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ResumeButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            paused.by_player = false;
+        }
+    }
+}
+
+/// Handles interactions with the quit to menu button. Bailing on a run counts as a rage quit.
+fn quit_to_menu_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    mut save_data: ResMut<SaveData>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<QuitToMenuButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            save_data.record_rage_quit();
+            save_data.save();
+            game_state.set(GameState::Menu).unwrap();
+        }
+    }
+}
+
+/// Handles freezing physics, animations, and the alarm while paused
+fn pause_effects_system(
+    paused: Res<Paused>,
+    audio: Res<Audio>,
+    alarm_channel: Res<AlarmChannel>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+    mut transform_animator_query: Query<&mut Animator<Transform>>,
+    mut ui_color_animator_query: Query<&mut Animator<UiColor>>,
+    mut sprite_animator_query: Query<&mut Animator<Sprite>>,
+) {
+    if !paused.is_changed() {
+        return;
+    }
+
+    let is_paused = paused.is_paused();
+    let animator_state = if is_paused {
+        AnimatorState::Paused
+    } else {
+        AnimatorState::Playing
+    };
+
+    rapier_config.physics_pipeline_active = !is_paused;
+
+    for mut animator in transform_animator_query.iter_mut() {
+        animator.state = animator_state;
+    }
+    for mut animator in ui_color_animator_query.iter_mut() {
+        animator.state = animator_state;
+    }
+    for mut animator in sprite_animator_query.iter_mut() {
+        animator.state = animator_state;
+    }
+
+    if is_paused {
+        audio.pause_channel(&alarm_channel.0);
+    } else {
+        audio.resume_channel(&alarm_channel.0);
+    }
+}
+
+/// Makes sure the game isn't left paused once it's over
+fn unpause(mut paused: ResMut<Paused>) {
+    if paused.is_paused() {
+        *paused = Paused::default();
+    }
+}
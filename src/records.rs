@@ -0,0 +1,150 @@
+use crate::*;
+
+pub struct RecordsPlugin;
+
+impl Plugin for RecordsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Records).with_system(records_setup))
+            .add_system_set(
+                SystemSet::on_exit(GameState::Records)
+                    .with_system(despawn_components_system::<RecordsComponent>),
+            )
+            .add_system(records_back_button_system);
+    }
+}
+
+#[derive(Component)]
+struct RecordsComponent;
+
+#[derive(Component)]
+struct RecordsBackButton;
+
+/// Sets up the records screen.
+fn records_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+) {
+    let font = asset_server.load(MAIN_FONT);
+    let ui_scale = save_data.settings.ui_scale();
+
+    let mut lines = Difficulty::ALL
+        .iter()
+        .map(|difficulty| {
+            score_line(
+                localization.localize(difficulty.name_key()),
+                save_data.high_scores.get(difficulty),
+                &localization,
+            )
+        })
+        .collect::<Vec<String>>();
+    let night_owl_name = localization
+        .localize(GameMode::NightOwl.name_key())
+        .to_string();
+    lines.push(match save_data.night_owl_record {
+        Some(minutes) => localization.localize_with(
+            "records.minutes_late",
+            &[("name", night_owl_name), ("minutes", minutes.to_string())],
+        ),
+        None => localization.localize_with("records.none", &[("name", night_owl_name)]),
+    });
+    for mode in [GameMode::Weekend, GameMode::Workday] {
+        lines.push(score_line(
+            localization.localize(mode.name_key()),
+            save_data.mode_high_scores.get(&mode),
+            &localization,
+        ));
+    }
+    let text = lines.join("\n");
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .insert(RecordsComponent)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    localization.localize("records.title"),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 70.0 * ui_scale,
+                        color: Color::WHITE,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                style: Style {
+                    margin: Rect::all(Val::Px(15.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    text,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 30.0 * ui_scale,
+                        color: Color::WHITE,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                style: Style {
+                    margin: Rect::all(Val::Px(15.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("common.back"),
+                RecordsBackButton,
+            );
+        });
+}
+
+/// The line for the record with the provided name, if there is one
+fn score_line(name: &str, score: Option<&Score>, localization: &Localization) -> String {
+    match score {
+        Some(score) => localization.localize_with(
+            "records.score",
+            &[
+                ("name", name.to_string()),
+                ("snoozes", score.snoozes.to_string()),
+                ("time", score.time.to_string()),
+            ],
+        ),
+        None => localization.localize_with("records.none", &[("name", name.to_string())]),
+    }
+}
+
+/// Handles interactions with the back button.
+fn records_back_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<RecordsBackButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Menu).unwrap();
+        }
+    }
+}
@@ -0,0 +1,630 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    hash::Hash,
+    path::PathBuf,
+};
+
+use bevy::{app::AppExit, window::WindowCloseRequested};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::*;
+
+const SAVE_FILE_NAME: &str = "save.json";
+/// Where saves get exported to and imported from, next to the real save file
+const TRANSFER_FILE_NAME: &str = "save_transfer.json";
+
+/// The version of the save format. Bump this and add a migration to `migrate` whenever the format changes.
+pub const SAVE_VERSION: u32 = 2;
+
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 2.0;
+
+/// Fewer solver iterations than this lets the arm's joints pull apart
+pub const MIN_PHYSICS_ITERATIONS: usize = 1;
+/// More solver iterations than this costs a lot without making the arm noticeably steadier
+pub const MAX_PHYSICS_ITERATIONS: usize = 16;
+
+/// The number of rage quits it takes to get a comment about it on the menu
+pub const RAGE_QUIT_THRESHOLD: u32 = 3;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SaveData::load())
+            .add_system(exit_system);
+    }
+}
+
+/// Everything that gets persisted between sessions
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveData {
+    pub version: u32,
+    pub settings: Settings,
+    pub stats: Stats,
+    pub high_scores: HashMap<Difficulty, Score>,
+    /// High scores for modes other than classic that are scored by snoozes
+    pub mode_high_scores: HashMap<GameMode, Score>,
+    /// The most minutes past the wake goal reached in night owl mode
+    pub night_owl_record: Option<u32>,
+    /// The most recent runs, newest first
+    pub history: VecDeque<RunRecord>,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        SaveData {
+            version: SAVE_VERSION,
+            settings: Settings::default(),
+            stats: Stats::default(),
+            high_scores: HashMap::new(),
+            mode_high_scores: HashMap::new(),
+            night_owl_record: None,
+            history: VecDeque::new(),
+        }
+    }
+}
+
+/// Player-configurable settings
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub track_rage_quits: bool,
+    /// Whether the press key has to be held down for a bit to snooze, rather than just tapped
+    pub hold_to_press: bool,
+    /// Whether each run starts the arm in a random pose, rather than always the same one
+    pub randomize_starting_pose: bool,
+    /// Whether the game pauses itself when the window loses focus
+    pub auto_pause_on_focus_loss: bool,
+    /// Whether the phone leaves a trail behind it when it's moving fast
+    pub phone_trail: bool,
+    /// Whether to show where the fingers are relative to the snooze button
+    pub finger_hints: bool,
+    /// Whether to keep the cursor visible while playing, rather than hiding it
+    pub show_cursor_in_game: bool,
+    /// Whether to subtly adjust how hard things are based on how the player is doing
+    pub dynamic_difficulty: bool,
+    /// The language to show text in
+    pub language: Language,
+    /// How big to make text and buttons, relative to their normal size
+    pub ui_scale: f32,
+    /// How many solver iterations the physics does each step. Lower is faster, higher keeps the arm steadier.
+    pub physics_iterations: usize,
+    /// Whether holding the extend key charges up a throw of the arm, rather than steadily extending it
+    pub charge_throw: bool,
+    /// Whether to show what time it'll be after the next snooze
+    pub snooze_preview: bool,
+    /// How long a menu navigation key has to be held before it starts repeating, in seconds
+    pub menu_repeat_delay: f32,
+    /// How often a held menu navigation key repeats once it's started, in seconds
+    pub menu_repeat_interval: f32,
+    /// Whether to skip the game over screen and start a new run right away
+    pub auto_retry: bool,
+    /// Whether the phone has a dismiss button for ending the run by getting up, alongside the snooze button
+    pub dismiss_button: bool,
+    /// Whether the game snoozes for the player once a run if the alarm goes on too long, even after they've learned how
+    pub extra_help: bool,
+    /// How long the alarm can go without the player doing anything before it starts getting more insistent, in seconds
+    pub idle_timeout_seconds: f32,
+    /// Turns off purely cosmetic motion effects
+    pub reduce_motion: bool,
+    /// Overall volume, from 0 to 1
+    pub master_volume: f32,
+    /// Volume of the alarm relative to everything else, from 0 to 1
+    pub alarm_volume: f32,
+    /// Volume of sound effects relative to everything else, from 0 to 1
+    pub sfx_volume: f32,
+    /// Volume of the menu music relative to everything else, from 0 to 1
+    pub music_volume: f32,
+    /// What the phone looks like
+    pub phone_skin: PhoneSkin,
+    /// What the alarm sounds like
+    pub alarm_tone: AlarmTone,
+    /// The keys used to control the hand and arm
+    pub key_bindings: KeyBindings,
+    /// Whether holding the left mouse button steers the hand toward the cursor, and clicking presses
+    pub mouse_control: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            track_rage_quits: true,
+            hold_to_press: false,
+            randomize_starting_pose: false,
+            auto_pause_on_focus_loss: true,
+            phone_trail: true,
+            finger_hints: false,
+            show_cursor_in_game: false,
+            dynamic_difficulty: false,
+            language: Language::default(),
+            ui_scale: 1.0,
+            physics_iterations: 4,
+            charge_throw: false,
+            snooze_preview: false,
+            menu_repeat_delay: 0.4,
+            menu_repeat_interval: 0.12,
+            auto_retry: false,
+            dismiss_button: false,
+            extra_help: false,
+            idle_timeout_seconds: 15.0,
+            reduce_motion: false,
+            master_volume: 1.0,
+            alarm_volume: 1.0,
+            sfx_volume: 1.0,
+            music_volume: 1.0,
+            phone_skin: PhoneSkin::default(),
+            alarm_tone: AlarmTone::default(),
+            key_bindings: KeyBindings::default(),
+            mouse_control: false,
+        }
+    }
+}
+
+impl Settings {
+    /// The UI scale to actually use, kept within reason so nothing ends up unreadable or off screen
+    pub fn ui_scale(&self) -> f32 {
+        self.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE)
+    }
+
+    /// The physics solver iterations to actually use, kept within what the arm's joints can handle
+    pub fn physics_iterations(&self) -> usize {
+        self.physics_iterations
+            .clamp(MIN_PHYSICS_ITERATIONS, MAX_PHYSICS_ITERATIONS)
+    }
+}
+
+/// Lifetime stats
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Stats {
+    pub rage_quits: u32,
+    pub total_snoozes: u32,
+    pub total_runs: u32,
+    /// The latest anyone has managed to stay in bed
+    pub best_wake_time: Option<GameTime>,
+    pub most_snoozes: u32,
+    pub seconds_played: f64,
+    /// How many runs ended with the player getting up on purpose
+    pub wins: u32,
+    /// How many of the total snoozes the game did for the player
+    pub assisted_snoozes: u32,
+}
+
+/// The result of a run
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Score {
+    pub snoozes: u32,
+    pub time: GameTime,
+}
+
+impl Score {
+    /// Whether this score is better than the provided one
+    pub fn beats(&self, other: &Score) -> bool {
+        self.snoozes > other.snoozes
+    }
+}
+
+/// Why a save couldn't be exported or imported
+pub enum TransferError {
+    /// There's no filesystem to transfer to or from
+    Unsupported,
+    Io(PathBuf, std::io::Error),
+    Corrupt(serde_json::Error),
+    /// The save is from a newer version of the game than this one
+    TooNew(u32),
+}
+
+impl std::fmt::Display for TransferError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransferError::Unsupported => write!(f, "saves can't be transferred on this platform"),
+            TransferError::Io(path, e) => write!(f, "couldn't access {}: {e}", path.display()),
+            TransferError::Corrupt(e) => write!(f, "not a valid save file: {e}"),
+            TransferError::TooNew(version) => write!(
+                f,
+                "save is from a newer version of the game (version {version}, this is version {SAVE_VERSION})"
+            ),
+        }
+    }
+}
+
+impl SaveData {
+    /// Loads the save data from disk, falling back to the defaults if it's missing or corrupt
+    pub fn load() -> SaveData {
+        let path = match save_path() {
+            Some(path) => path,
+            None => return SaveData::default(),
+        };
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(_) => return SaveData::default(),
+        };
+
+        let newer_version = serde_json::from_str(&contents)
+            .ok()
+            .as_ref()
+            .and_then(newer_save_version);
+        if let Some(version) = newer_version {
+            // playing on the defaults would overwrite it at the next save, so it gets moved out of the way instead
+            let backup_path = path.with_file_name(format!("save_v{version}.json"));
+            warn!(
+                "save file at {} is from a newer version of the game (version {version}, this is version {SAVE_VERSION}), moving it to {}",
+                path.display(),
+                backup_path.display()
+            );
+            if let Err(e) = fs::rename(&path, &backup_path) {
+                warn!("couldn't move save file to {}: {e}", backup_path.display());
+            }
+            return SaveData::default();
+        }
+
+        SaveData::from_json(&contents).unwrap_or_else(|e| {
+            warn!("couldn't parse save file at {}: {e}", path.display());
+            SaveData::default()
+        })
+    }
+
+    /// Parses save data from JSON, upgrading it from older versions of the format if needed
+    pub fn from_json(contents: &str) -> Result<SaveData, serde_json::Error> {
+        let mut value: Value = serde_json::from_str(contents)?;
+        migrate(&mut value);
+        serde_json::from_value(value)
+    }
+
+    /// Serializes the save data to JSON, in the form `from_json` reads back
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Writes the save data to disk. This blocks until the write is done, so it's safe to call right before exiting.
+    pub fn save(&self) {
+        let path = match save_path() {
+            Some(path) => path,
+            None => return,
+        };
+
+        if let Some(parent) = path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                warn!("couldn't create save directory {}: {e}", parent.display());
+                return;
+            }
+        }
+
+        match self.to_json() {
+            Ok(contents) => {
+                if let Err(e) = fs::write(&path, contents) {
+                    warn!("couldn't write save file to {}: {e}", path.display());
+                }
+            }
+            Err(e) => warn!("couldn't serialize save data: {e}"),
+        }
+    }
+
+    /// Writes a copy of the save data to the transfer file, for backing it up or moving it to another machine.
+    /// Returns where it was written to.
+    pub fn export(&self) -> Result<PathBuf, TransferError> {
+        let path = transfer_path().ok_or(TransferError::Unsupported)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| TransferError::Io(parent.to_path_buf(), e))?;
+        }
+        let contents = self.to_json().map_err(TransferError::Corrupt)?;
+        fs::write(&path, contents).map_err(|e| TransferError::Io(path.clone(), e))?;
+
+        Ok(path)
+    }
+
+    /// Reads save data from the transfer file, making sure it's something this version of the game can use
+    pub fn import() -> Result<SaveData, TransferError> {
+        let path = transfer_path().ok_or(TransferError::Unsupported)?;
+        let contents = fs::read_to_string(&path).map_err(|e| TransferError::Io(path, e))?;
+
+        let value: Value = serde_json::from_str(&contents).map_err(TransferError::Corrupt)?;
+        if let Some(version) = newer_save_version(&value) {
+            return Err(TransferError::TooNew(version));
+        }
+
+        SaveData::from_json(&contents).map_err(TransferError::Corrupt)
+    }
+
+    /// Records the score from a run if it's the best one for its difficulty, and saves if so.
+    /// Returns whether it was a new high score.
+    pub fn record_score(&mut self, difficulty: Difficulty, score: Score) -> bool {
+        let is_high_score = record_in(&mut self.high_scores, difficulty, score);
+        if is_high_score {
+            self.save();
+        }
+
+        is_high_score
+    }
+
+    /// Records the score from a run if it's the best one for its mode, and saves if so.
+    /// Returns whether it was a new high score.
+    pub fn record_mode_score(&mut self, mode: GameMode, score: Score) -> bool {
+        let is_high_score = record_in(&mut self.mode_high_scores, mode, score);
+        if is_high_score {
+            self.save();
+        }
+
+        is_high_score
+    }
+
+    /// Records the number of minutes past the wake goal reached in a night owl run if it's the best one, and saves if so.
+    /// Returns whether it was a new record.
+    pub fn record_night_owl_score(&mut self, minutes_past_goal: u32) -> bool {
+        let is_record = match self.night_owl_record {
+            Some(record) => minutes_past_goal > record,
+            None => true,
+        };
+
+        if is_record {
+            self.night_owl_record = Some(minutes_past_goal);
+            self.save();
+        }
+
+        is_record
+    }
+
+    /// The best score so far for the same kind of run as the provided one, or `None` if there isn't one or that kind of run isn't scored by snoozes
+    pub fn best_score_for(&self, result: &GameResult) -> Option<Score> {
+        match result.mode {
+            GameMode::Classic => self.high_scores.get(&result.difficulty).copied(),
+            GameMode::Weekend | GameMode::Workday => {
+                self.mode_high_scores.get(&result.mode).copied()
+            }
+            GameMode::NightOwl => None,
+        }
+    }
+
+    /// Records everything that depends on how a run went: the history, the lifetime stats, and the high scores.
+    /// Saves afterwards.
+    pub fn record_result(&mut self, result: &GameResult) {
+        self.record_history(RunRecord::new(result));
+        if result.won {
+            self.record_win();
+        }
+        self.record_run_end(result.score());
+        match result.mode {
+            GameMode::Classic => {
+                self.record_score(result.difficulty, result.score());
+            }
+            GameMode::Weekend | GameMode::Workday => {
+                self.record_mode_score(result.mode, result.score());
+            }
+            GameMode::NightOwl => {
+                if let Some(minutes_past_goal) = result.minutes_past_goal() {
+                    self.record_night_owl_score(minutes_past_goal);
+                }
+            }
+        }
+    }
+
+    /// Records the end of a run in the lifetime stats, and saves
+    pub fn record_run_end(&mut self, score: Score) {
+        self.stats.most_snoozes = self.stats.most_snoozes.max(score.snoozes);
+        let is_best_wake_time = match self.stats.best_wake_time {
+            Some(best) => score.time.minutes_past(&best) > 0,
+            None => true,
+        };
+        if is_best_wake_time {
+            self.stats.best_wake_time = Some(score.time);
+        }
+
+        self.save();
+    }
+
+    /// Records that the player got up on purpose.
+    /// This doesn't save on its own, since it's always followed by `record_run_end`.
+    pub fn record_win(&mut self) {
+        self.stats.wins += 1;
+    }
+
+    /// Whether something that takes the provided number of wins to unlock has been unlocked
+    pub fn is_unlocked(&self, required_wins: u32) -> bool {
+        self.stats.wins >= required_wins
+    }
+
+    /// Adds a run to the history, dropping the oldest one if there are too many.
+    /// This doesn't save on its own, since it's always followed by `record_run_end`.
+    pub fn record_history(&mut self, record: RunRecord) {
+        self.history.push_front(record);
+        self.history.truncate(MAX_HISTORY_LENGTH);
+    }
+
+    /// Records that the player quit in the middle of a run
+    pub fn record_rage_quit(&mut self) {
+        if self.settings.track_rage_quits {
+            self.stats.rage_quits += 1;
+        }
+    }
+
+    /// Whether the player has rage quit enough times to deserve a comment about it
+    pub fn is_rage_quitter(&self) -> bool {
+        self.settings.track_rage_quits && self.stats.rage_quits >= RAGE_QUIT_THRESHOLD
+    }
+}
+
+/// Puts the provided score in the provided high scores under the provided key if it beats the one already there.
+/// Returns whether it was a new high score.
+fn record_in<K: Eq + Hash>(high_scores: &mut HashMap<K, Score>, key: K, score: Score) -> bool {
+    let is_high_score = match high_scores.get(&key) {
+        Some(high_score) => score.beats(high_score),
+        None => true,
+    };
+
+    if is_high_score {
+        high_scores.insert(key, score);
+    }
+
+    is_high_score
+}
+
+/// Upgrades serialized save data from whatever version it's in to the current one
+fn migrate(value: &mut Value) {
+    let object = match value.as_object_mut() {
+        Some(object) => object,
+        None => return,
+    };
+
+    // saves from before versioning are version 1
+    let mut version = object.get("version").and_then(Value::as_u64).unwrap_or(1) as u32;
+
+    while version < SAVE_VERSION {
+        if version == 1 {
+            // version 1 only had one high score, which was always on normal
+            if let Some(high_score) = object.remove("high_score") {
+                if !high_score.is_null() {
+                    let high_scores = object.entry("high_scores").or_insert_with(|| json!({}));
+                    // keyed the same way serde writes difficulties out
+                    let normal_key = serde_json::to_value(Difficulty::Normal);
+                    if let (Some(high_scores), Ok(Value::String(normal_key))) =
+                        (high_scores.as_object_mut(), normal_key)
+                    {
+                        high_scores.entry(normal_key).or_insert(high_score);
+                    }
+                }
+            }
+        }
+
+        version += 1;
+    }
+
+    object.insert("version".to_string(), json!(version));
+}
+
+/// The version of the game the provided serialized save data is from, if it's newer than this one
+fn newer_save_version(value: &Value) -> Option<u32> {
+    value
+        .get("version")
+        .and_then(Value::as_u64)
+        .filter(|version| *version > u64::from(SAVE_VERSION))
+        .map(|version| u32::try_from(version).unwrap_or(u32::MAX))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "Snooze")
+        .map(|dirs| dirs.config_dir().join(SAVE_FILE_NAME))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_path() -> Option<PathBuf> {
+    // no filesystem on the web
+    None
+}
+
+/// Where saves get exported to and imported from
+pub fn transfer_path() -> Option<PathBuf> {
+    save_path().map(|path| path.with_file_name(TRANSFER_FILE_NAME))
+}
+
+/// Handles flushing the save data to disk when the app is closing, whether from the window being closed or otherwise.
+/// On the web there's nowhere to save to, so this does nothing there.
+fn exit_system(
+    game_state: Res<State<GameState>>,
+    mut save_data: ResMut<SaveData>,
+    mut close_events: EventReader<WindowCloseRequested>,
+    mut exit_events: EventReader<AppExit>,
+) {
+    let quitting = close_events.iter().next().is_some() || exit_events.iter().next().is_some();
+    if !quitting {
+        return;
+    }
+
+    if *game_state.current() == GameState::Game {
+        save_data.record_rage_quit();
+    }
+
+    save_data.save();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_v1_high_score_to_normal() {
+        let v1 = r#"{ "high_score": { "snoozes": 7, "time": { "hour": 9, "minute": 10 } } }"#;
+
+        let save_data = SaveData::from_json(v1).unwrap();
+
+        let high_score = save_data.high_scores.get(&Difficulty::Normal).unwrap();
+        assert_eq!(7, high_score.snoozes);
+        assert_eq!(9, high_score.time.hour);
+        assert_eq!(10, high_score.time.minute);
+        assert_eq!(1, save_data.high_scores.len());
+        assert_eq!(SAVE_VERSION, save_data.version);
+        assert_eq!(
+            serde_json::to_value(Settings::default()).unwrap(),
+            serde_json::to_value(&save_data.settings).unwrap()
+        );
+    }
+
+    #[test]
+    fn migrates_v1_without_high_score() {
+        let save_data = SaveData::from_json(r#"{ "high_score": null }"#).unwrap();
+
+        assert!(save_data.high_scores.is_empty());
+        assert_eq!(SAVE_VERSION, save_data.version);
+    }
+
+    #[test]
+    fn current_version_is_left_alone() {
+        let saved = SaveData::default();
+        let contents = serde_json::to_string(&saved).unwrap();
+
+        let loaded = SaveData::from_json(&contents).unwrap();
+
+        assert_eq!(SAVE_VERSION, loaded.version);
+        assert!(loaded.high_scores.is_empty());
+    }
+
+    #[test]
+    fn saved_data_loads_back_the_same() {
+        let mut saved = SaveData::default();
+        saved.settings.hold_to_press = !saved.settings.hold_to_press;
+        saved.stats.rage_quits = 2;
+        saved.high_scores.insert(
+            Difficulty::Hard,
+            Score {
+                snoozes: 12,
+                time: GameTime {
+                    hour: 9,
+                    minute: 24,
+                },
+            },
+        );
+        saved.night_owl_record = Some(45);
+
+        let loaded = SaveData::from_json(&saved.to_json().unwrap()).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&saved).unwrap(),
+            serde_json::to_value(&loaded).unwrap()
+        );
+    }
+
+    #[test]
+    fn newer_versions_are_detected() {
+        let newer = json!({ "version": SAVE_VERSION + 1 });
+        let current = serde_json::to_value(SaveData::default()).unwrap();
+        let v1 = json!({ "high_score": null });
+
+        assert_eq!(Some(SAVE_VERSION + 1), newer_save_version(&newer));
+        assert_eq!(None, newer_save_version(&current));
+        assert_eq!(None, newer_save_version(&v1));
+    }
+
+    #[test]
+    fn migrating_keeps_newer_versions() {
+        let mut newer = json!({ "version": SAVE_VERSION + 1 });
+
+        migrate(&mut newer);
+
+        assert_eq!(Some(SAVE_VERSION + 1), newer_save_version(&newer));
+    }
+}
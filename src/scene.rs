@@ -0,0 +1,127 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use bevy_asset_loader::AssetCollection;
+use serde::Deserialize;
+
+use crate::*;
+
+pub struct SceneConfigPlugin;
+
+impl Plugin for SceneConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<SceneConfig>()
+            .init_asset_loader::<SceneConfigLoader>();
+    }
+}
+
+#[derive(AssetCollection)]
+pub struct SceneAssets {
+    #[asset(path = "scenes/bedroom.scene.ron")]
+    pub bedroom: Handle<SceneConfig>,
+}
+
+/// The layout of everything in a game scene
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "5f0c2f6e-0d0a-4b8e-9a4f-3c6d2f1b7e21"]
+pub struct SceneConfig {
+    pub background: SpriteConfig,
+    pub phone: SpriteConfig,
+    pub time_display: TextConfig,
+    pub snooze_button: ButtonConfig,
+    /// The button for getting up instead of snoozing, when that's turned on
+    #[serde(default)]
+    pub dismiss_button: Option<ButtonConfig>,
+    pub arm: BodyConfig,
+    pub hand: BodyConfig,
+    /// The parts of the hand that can press buttons, relative to the hand
+    pub touch_areas: Vec<TouchAreaConfig>,
+    /// Attaches the arm to the arm anchor
+    pub arm_joint: JointConfig,
+    /// Attaches the hand to the arm
+    pub hand_joint: JointConfig,
+    /// Things on the table the hand has to get around, on difficulties that have them
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleConfig>,
+}
+
+#[derive(Deserialize)]
+pub struct SpriteConfig {
+    pub position: [f32; 3],
+    pub scale: [f32; 3],
+}
+
+#[derive(Deserialize)]
+pub struct BodyConfig {
+    pub position: [f32; 3],
+    pub scale: [f32; 3],
+    pub collider_radius: f32,
+}
+
+#[derive(Deserialize)]
+pub struct TextConfig {
+    pub position: [f32; 3],
+    pub font_size: f32,
+    pub color: [f32; 4],
+}
+
+#[derive(Deserialize)]
+pub struct ButtonConfig {
+    pub position: [f32; 3],
+    pub size: [f32; 2],
+    pub color: [f32; 4],
+    pub text: TextConfig,
+}
+
+#[derive(Deserialize)]
+pub struct TouchAreaConfig {
+    pub name: String,
+    pub finger: Finger,
+    pub position: [f32; 3],
+    pub size: [f32; 2],
+}
+
+#[derive(Deserialize)]
+pub struct ObstacleConfig {
+    pub name: String,
+    pub position: [f32; 3],
+    pub size: [f32; 2],
+    pub color: [f32; 4],
+    /// Whether bumping into this counts against the player
+    #[serde(default)]
+    pub hazard: bool,
+}
+
+#[derive(Deserialize)]
+pub struct JointConfig {
+    pub anchor1: [f32; 2],
+    pub anchor2: [f32; 2],
+}
+
+/// Converts an RGBA array from a config into a color
+pub fn config_color(rgba: [f32; 4]) -> Color {
+    Color::rgba(rgba[0], rgba[1], rgba[2], rgba[3])
+}
+
+#[derive(Default)]
+struct SceneConfigLoader;
+
+impl AssetLoader for SceneConfigLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let config = ron::de::from_bytes::<SceneConfig>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(config));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scene.ron"]
+    }
+}
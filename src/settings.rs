@@ -0,0 +1,742 @@
+use crate::*;
+
+/// How much each press of a volume button changes the volume by
+const VOLUME_STEP: f32 = 0.1;
+
+/// How many toggle buttons go in each column on the settings screen
+const TOGGLES_PER_COLUMN: usize = 8;
+
+/// The shortest and longest a menu navigation key can be held before it starts repeating, in seconds
+const MENU_REPEAT_DELAY_RANGE: (f32, f32) = (0.1, 1.0);
+/// The longest a held menu navigation key can take between repeats, in seconds
+const MAX_MENU_REPEAT_INTERVAL: f32 = 0.5;
+/// The shortest and longest the player can ignore the alarm before it starts escalating, in seconds
+const IDLE_TIMEOUT_RANGE: (f32, f32) = (5.0, 60.0);
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Settings).with_system(settings_setup))
+            .add_system_set(
+                SystemSet::on_exit(GameState::Settings)
+                    .with_system(despawn_components_system::<SettingsComponent>),
+            )
+            .add_system(number_button_system)
+            .add_system(toggle_button_system)
+            .add_system(language_button_system)
+            .add_system(controls_button_system)
+            .add_system(settings_back_button_system);
+    }
+}
+
+#[derive(Component)]
+struct SettingsComponent;
+
+#[derive(Component)]
+struct SettingsBackButton;
+
+#[derive(Component)]
+struct ControlsButton;
+
+/// A button for switching to the next language
+#[derive(Component)]
+struct LanguageButton;
+
+/// A setting with a number that can be turned up or down on the settings screen
+#[derive(Clone, Copy, PartialEq)]
+enum NumberSetting {
+    MasterVolume,
+    AlarmVolume,
+    SfxVolume,
+    MusicVolume,
+    UiScale,
+    PhysicsIterations,
+    MenuRepeatDelay,
+    MenuRepeatInterval,
+    IdleTimeout,
+}
+
+impl NumberSetting {
+    const ALL: [NumberSetting; 9] = [
+        NumberSetting::MasterVolume,
+        NumberSetting::AlarmVolume,
+        NumberSetting::SfxVolume,
+        NumberSetting::MusicVolume,
+        NumberSetting::UiScale,
+        NumberSetting::PhysicsIterations,
+        NumberSetting::MenuRepeatDelay,
+        NumberSetting::MenuRepeatInterval,
+        NumberSetting::IdleTimeout,
+    ];
+
+    /// The key for the name of this setting
+    fn name_key(&self) -> &'static str {
+        match self {
+            NumberSetting::MasterVolume => "settings.master_volume",
+            NumberSetting::AlarmVolume => "settings.alarm_volume",
+            NumberSetting::SfxVolume => "settings.sfx_volume",
+            NumberSetting::MusicVolume => "settings.music_volume",
+            NumberSetting::UiScale => "settings.ui_scale",
+            NumberSetting::PhysicsIterations => "settings.physics_iterations",
+            NumberSetting::MenuRepeatDelay => "settings.menu_repeat_delay",
+            NumberSetting::MenuRepeatInterval => "settings.menu_repeat_interval",
+            NumberSetting::IdleTimeout => "settings.idle_timeout",
+        }
+    }
+
+    /// How much each press of a button for this changes it by
+    fn step(&self) -> f32 {
+        match self {
+            NumberSetting::MasterVolume
+            | NumberSetting::AlarmVolume
+            | NumberSetting::SfxVolume
+            | NumberSetting::MusicVolume => VOLUME_STEP,
+            NumberSetting::UiScale => 0.1,
+            NumberSetting::PhysicsIterations => 1.0,
+            NumberSetting::MenuRepeatDelay => 0.05,
+            NumberSetting::MenuRepeatInterval => 0.02,
+            NumberSetting::IdleTimeout => 5.0,
+        }
+    }
+
+    /// The lowest and highest this can be set to
+    fn range(&self) -> (f32, f32) {
+        match self {
+            NumberSetting::MasterVolume
+            | NumberSetting::AlarmVolume
+            | NumberSetting::SfxVolume
+            | NumberSetting::MusicVolume => (0.0, 1.0),
+            NumberSetting::UiScale => (MIN_UI_SCALE, MAX_UI_SCALE),
+            NumberSetting::PhysicsIterations => {
+                (MIN_PHYSICS_ITERATIONS as f32, MAX_PHYSICS_ITERATIONS as f32)
+            }
+            NumberSetting::MenuRepeatDelay => MENU_REPEAT_DELAY_RANGE,
+            NumberSetting::MenuRepeatInterval => (MIN_REPEAT_SECONDS, MAX_MENU_REPEAT_INTERVAL),
+            NumberSetting::IdleTimeout => IDLE_TIMEOUT_RANGE,
+        }
+    }
+
+    /// What this is set to, out of the provided settings
+    fn value(&self, settings: &Settings) -> f32 {
+        match self {
+            NumberSetting::MasterVolume => settings.master_volume,
+            NumberSetting::AlarmVolume => settings.alarm_volume,
+            NumberSetting::SfxVolume => settings.sfx_volume,
+            NumberSetting::MusicVolume => settings.music_volume,
+            NumberSetting::UiScale => settings.ui_scale,
+            NumberSetting::PhysicsIterations => settings.physics_iterations as f32,
+            NumberSetting::MenuRepeatDelay => settings.menu_repeat_delay,
+            NumberSetting::MenuRepeatInterval => settings.menu_repeat_interval,
+            NumberSetting::IdleTimeout => settings.idle_timeout_seconds,
+        }
+    }
+
+    /// Changes what this is set to in the provided settings
+    fn set_value(&self, settings: &mut Settings, value: f32) {
+        match self {
+            NumberSetting::MasterVolume => settings.master_volume = value,
+            NumberSetting::AlarmVolume => settings.alarm_volume = value,
+            NumberSetting::SfxVolume => settings.sfx_volume = value,
+            NumberSetting::MusicVolume => settings.music_volume = value,
+            NumberSetting::UiScale => settings.ui_scale = value,
+            NumberSetting::PhysicsIterations => {
+                settings.physics_iterations = value.round() as usize
+            }
+            NumberSetting::MenuRepeatDelay => settings.menu_repeat_delay = value,
+            NumberSetting::MenuRepeatInterval => settings.menu_repeat_interval = value,
+            NumberSetting::IdleTimeout => settings.idle_timeout_seconds = value,
+        }
+    }
+
+    /// Turns this up or down by a step in the provided settings
+    fn adjust(&self, settings: &mut Settings, up: bool) {
+        let step = if up { self.step() } else { -self.step() };
+        let (min, max) = self.range();
+        // snap to the nearest step so repeated presses don't pile up rounding errors
+        let value =
+            (((self.value(settings) + step) / self.step()).round() * self.step()).clamp(min, max);
+        self.set_value(settings, value);
+    }
+
+    /// The text showing this setting, out of the provided settings
+    fn label(&self, settings: &Settings, localization: &Localization) -> String {
+        let value = self.value(settings);
+        let value = match self {
+            NumberSetting::MasterVolume
+            | NumberSetting::AlarmVolume
+            | NumberSetting::SfxVolume
+            | NumberSetting::MusicVolume
+            | NumberSetting::UiScale => format!("{:.0}%", value * 100.0),
+            NumberSetting::PhysicsIterations => format!("{value:.0}"),
+            NumberSetting::MenuRepeatDelay | NumberSetting::MenuRepeatInterval => {
+                format!("{value:.2}s")
+            }
+            NumberSetting::IdleTimeout => format!("{value:.0}s"),
+        };
+
+        localization.localize_with(
+            "settings.value",
+            &[
+                ("name", localization.localize(self.name_key()).to_string()),
+                ("value", value),
+            ],
+        )
+    }
+}
+
+/// A button for turning a number setting up or down by a step
+#[derive(Component)]
+struct NumberButton {
+    setting: NumberSetting,
+    up: bool,
+}
+
+/// Shows what a number setting is currently set to
+#[derive(Component)]
+struct NumberText(NumberSetting);
+
+/// A setting that's either on or off, which can be flipped on the settings screen
+#[derive(Clone, Copy)]
+enum ToggleSetting {
+    MouseControl,
+    AutoPause,
+    AutoRetry,
+    ReduceMotion,
+    PhoneTrail,
+    HoldToPress,
+    ChargeThrow,
+    DismissButton,
+    SnoozePreview,
+    ShowCursor,
+    TrackRageQuits,
+    DynamicDifficulty,
+    RandomizeStartingPose,
+    FingerHints,
+    ExtraHelp,
+}
+
+impl ToggleSetting {
+    const ALL: [ToggleSetting; 15] = [
+        ToggleSetting::MouseControl,
+        ToggleSetting::AutoPause,
+        ToggleSetting::AutoRetry,
+        ToggleSetting::ReduceMotion,
+        ToggleSetting::PhoneTrail,
+        ToggleSetting::HoldToPress,
+        ToggleSetting::ChargeThrow,
+        ToggleSetting::DismissButton,
+        ToggleSetting::SnoozePreview,
+        ToggleSetting::ShowCursor,
+        ToggleSetting::TrackRageQuits,
+        ToggleSetting::DynamicDifficulty,
+        ToggleSetting::RandomizeStartingPose,
+        ToggleSetting::FingerHints,
+        ToggleSetting::ExtraHelp,
+    ];
+
+    /// The key for the name of this setting
+    fn name_key(&self) -> &'static str {
+        match self {
+            ToggleSetting::MouseControl => "settings.mouse_control",
+            ToggleSetting::AutoPause => "settings.auto_pause",
+            ToggleSetting::AutoRetry => "settings.auto_retry",
+            ToggleSetting::ReduceMotion => "settings.reduce_motion",
+            ToggleSetting::PhoneTrail => "settings.phone_trail",
+            ToggleSetting::HoldToPress => "settings.hold_to_press",
+            ToggleSetting::ChargeThrow => "settings.charge_throw",
+            ToggleSetting::DismissButton => "settings.dismiss_button",
+            ToggleSetting::SnoozePreview => "settings.snooze_preview",
+            ToggleSetting::ShowCursor => "settings.show_cursor",
+            ToggleSetting::TrackRageQuits => "settings.track_rage_quits",
+            ToggleSetting::DynamicDifficulty => "settings.dynamic_difficulty",
+            ToggleSetting::RandomizeStartingPose => "settings.randomize_starting_pose",
+            ToggleSetting::FingerHints => "settings.finger_hints",
+            ToggleSetting::ExtraHelp => "settings.extra_help",
+        }
+    }
+
+    /// Whether this is on, out of the provided settings
+    fn enabled(&self, settings: &Settings) -> bool {
+        match self {
+            ToggleSetting::MouseControl => settings.mouse_control,
+            ToggleSetting::AutoPause => settings.auto_pause_on_focus_loss,
+            ToggleSetting::AutoRetry => settings.auto_retry,
+            ToggleSetting::ReduceMotion => settings.reduce_motion,
+            ToggleSetting::PhoneTrail => settings.phone_trail,
+            ToggleSetting::HoldToPress => settings.hold_to_press,
+            ToggleSetting::ChargeThrow => settings.charge_throw,
+            ToggleSetting::DismissButton => settings.dismiss_button,
+            ToggleSetting::SnoozePreview => settings.snooze_preview,
+            ToggleSetting::ShowCursor => settings.show_cursor_in_game,
+            ToggleSetting::TrackRageQuits => settings.track_rage_quits,
+            ToggleSetting::DynamicDifficulty => settings.dynamic_difficulty,
+            ToggleSetting::RandomizeStartingPose => settings.randomize_starting_pose,
+            ToggleSetting::FingerHints => settings.finger_hints,
+            ToggleSetting::ExtraHelp => settings.extra_help,
+        }
+    }
+
+    /// Whether this is on, out of the provided settings, for changing it
+    fn enabled_mut<'a>(&self, settings: &'a mut Settings) -> &'a mut bool {
+        match self {
+            ToggleSetting::MouseControl => &mut settings.mouse_control,
+            ToggleSetting::AutoPause => &mut settings.auto_pause_on_focus_loss,
+            ToggleSetting::AutoRetry => &mut settings.auto_retry,
+            ToggleSetting::ReduceMotion => &mut settings.reduce_motion,
+            ToggleSetting::PhoneTrail => &mut settings.phone_trail,
+            ToggleSetting::HoldToPress => &mut settings.hold_to_press,
+            ToggleSetting::ChargeThrow => &mut settings.charge_throw,
+            ToggleSetting::DismissButton => &mut settings.dismiss_button,
+            ToggleSetting::SnoozePreview => &mut settings.snooze_preview,
+            ToggleSetting::ShowCursor => &mut settings.show_cursor_in_game,
+            ToggleSetting::TrackRageQuits => &mut settings.track_rage_quits,
+            ToggleSetting::DynamicDifficulty => &mut settings.dynamic_difficulty,
+            ToggleSetting::RandomizeStartingPose => &mut settings.randomize_starting_pose,
+            ToggleSetting::FingerHints => &mut settings.finger_hints,
+            ToggleSetting::ExtraHelp => &mut settings.extra_help,
+        }
+    }
+
+    /// The label for the button for this, out of the provided settings
+    fn label(&self, settings: &Settings, localization: &Localization) -> String {
+        toggle_label(self.name_key(), self.enabled(settings), localization)
+    }
+}
+
+/// A button for turning a setting on and off
+#[derive(Component)]
+struct ToggleButton(ToggleSetting);
+
+/// Spawns a small button for turning the provided number setting up or down
+fn spawn_number_button(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    ui_scale: f32,
+    setting: NumberSetting,
+    up: bool,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(40.0 * ui_scale), Val::Px(40.0 * ui_scale)),
+                margin: Rect::all(Val::Px(4.0 * ui_scale)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .insert(NumberButton { setting, up })
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    if up { "+" } else { "-" },
+                    TextStyle {
+                        font,
+                        font_size: 30.0 * ui_scale,
+                        color: Color::SEA_GREEN,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                ..Default::default()
+            });
+        });
+}
+
+/// Spawns a wide button on the settings screen with the provided label
+fn spawn_settings_button<T: Component>(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    ui_scale: f32,
+    label: String,
+    marker: T,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(360.0 * ui_scale), Val::Px(40.0 * ui_scale)),
+                margin: Rect::all(Val::Px(4.0 * ui_scale)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .insert(marker)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    label,
+                    TextStyle {
+                        font,
+                        font_size: 20.0 * ui_scale,
+                        color: Color::SEA_GREEN,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                ..Default::default()
+            });
+        });
+}
+
+/// Spawns a column for grouping settings side by side
+fn spawn_settings_column(
+    parent: &mut ChildBuilder,
+    spawn_children: impl FnOnce(&mut ChildBuilder),
+) {
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .with_children(spawn_children);
+}
+
+/// The label for a button that turns something on and off, with the localized name of what it's for
+pub fn toggle_label(name_key: &str, on: bool, localization: &Localization) -> String {
+    let state_key = if on { "settings.on" } else { "settings.off" };
+    localization.localize_with(
+        "settings.toggle",
+        &[
+            ("name", localization.localize(name_key).to_string()),
+            ("state", localization.localize(state_key).to_string()),
+        ],
+    )
+}
+
+/// The label for the language button
+fn language_label(settings: &Settings, localization: &Localization) -> String {
+    localization.localize_with(
+        "settings.language",
+        &[("language", settings.language.name().to_string())],
+    )
+}
+
+/// Sets up the settings screen.
+fn settings_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+) {
+    spawn_settings_screen(
+        &mut commands,
+        asset_server.load(MAIN_FONT),
+        &save_data,
+        &localization,
+    );
+}
+
+/// Spawns everything on the settings screen, with its text in the provided language
+fn spawn_settings_screen(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    save_data: &SaveData,
+    localization: &Localization,
+) {
+    let ui_scale = save_data.settings.ui_scale();
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .insert(SettingsComponent)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    localization.localize("settings.title"),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 70.0 * ui_scale,
+                        color: Color::WHITE,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                style: Style {
+                    margin: Rect::all(Val::Px(15.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    color: UiColor(Color::NONE),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    spawn_settings_column(parent, |parent| {
+                        for setting in NumberSetting::ALL {
+                            parent
+                                .spawn_bundle(NodeBundle {
+                                    style: Style {
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    color: UiColor(Color::NONE),
+                                    ..Default::default()
+                                })
+                                .with_children(|parent| {
+                                    spawn_number_button(
+                                        parent,
+                                        font.clone(),
+                                        ui_scale,
+                                        setting,
+                                        false,
+                                    );
+                                    parent
+                                        .spawn_bundle(TextBundle {
+                                            text: Text::with_section(
+                                                setting.label(&save_data.settings, localization),
+                                                TextStyle {
+                                                    font: font.clone(),
+                                                    font_size: 20.0 * ui_scale,
+                                                    color: Color::WHITE,
+                                                },
+                                                TextAlignment {
+                                                    horizontal: HorizontalAlign::Center,
+                                                    ..Default::default()
+                                                },
+                                            ),
+                                            style: Style {
+                                                size: Size::new(
+                                                    Val::Px(280.0 * ui_scale),
+                                                    Val::Auto,
+                                                ),
+                                                margin: Rect::all(Val::Px(5.0 * ui_scale)),
+                                                ..Default::default()
+                                            },
+                                            ..Default::default()
+                                        })
+                                        .insert(NumberText(setting));
+                                    spawn_number_button(
+                                        parent,
+                                        font.clone(),
+                                        ui_scale,
+                                        setting,
+                                        true,
+                                    );
+                                });
+                        }
+                    });
+
+                    let columns = ToggleSetting::ALL.chunks(TOGGLES_PER_COLUMN);
+                    let last_column = columns.len() - 1;
+                    for (i, toggles) in columns.enumerate() {
+                        spawn_settings_column(parent, |parent| {
+                            for setting in toggles {
+                                spawn_settings_button(
+                                    parent,
+                                    font.clone(),
+                                    ui_scale,
+                                    setting.label(&save_data.settings, localization),
+                                    ToggleButton(*setting),
+                                );
+                            }
+
+                            if i == last_column {
+                                spawn_settings_button(
+                                    parent,
+                                    font.clone(),
+                                    ui_scale,
+                                    language_label(&save_data.settings, localization),
+                                    LanguageButton,
+                                );
+                            }
+                        });
+                    }
+                });
+
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: UiColor(Color::NONE),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    spawn_button(
+                        parent,
+                        font.clone(),
+                        ui_scale,
+                        localization.localize("settings.controls"),
+                        ControlsButton,
+                    );
+                    spawn_button(
+                        parent,
+                        font.clone(),
+                        ui_scale,
+                        localization.localize("common.back"),
+                        SettingsBackButton,
+                    );
+                });
+        });
+}
+
+/// Despawns the settings screen and spawns it again, for when something that affects all of it changes
+fn respawn_settings_screen(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    screen_query: Query<Entity, With<SettingsComponent>>,
+    save_data: &SaveData,
+    localization: &Localization,
+) {
+    despawn_components(screen_query, commands);
+    spawn_settings_screen(
+        commands,
+        asset_server.load(MAIN_FONT),
+        save_data,
+        localization,
+    );
+}
+
+/// Handles turning number settings up and down. The new value takes effect right away for anything that reads it
+/// from the settings as it goes, like volumes, and at the start of the next run for everything else.
+fn number_button_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut save_data: ResMut<SaveData>,
+    localization: Res<Localization>,
+    interaction_query: Query<(&Interaction, &NumberButton), Changed<Interaction>>,
+    mut text_query: Query<(&mut Text, &NumberText)>,
+    screen_query: Query<Entity, With<SettingsComponent>>,
+) {
+    let mut changed = false;
+    let mut rescaled = false;
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        button.setting.adjust(&mut save_data.settings, button.up);
+        changed = true;
+        rescaled |= button.setting == NumberSetting::UiScale;
+    }
+
+    if !changed {
+        return;
+    }
+
+    save_data.save();
+    if rescaled {
+        // everything on the screen is sized by the UI scale, so it all has to be redone
+        respawn_settings_screen(
+            &mut commands,
+            &asset_server,
+            screen_query,
+            &save_data,
+            &localization,
+        );
+        return;
+    }
+
+    for (mut text, number_text) in text_query.iter_mut() {
+        text.sections[0].value = number_text.0.label(&save_data.settings, &localization);
+    }
+}
+
+/// Handles turning settings on and off
+fn toggle_button_system(
+    mut save_data: ResMut<SaveData>,
+    localization: Res<Localization>,
+    interaction_query: Query<(&Interaction, &ToggleButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (interaction, button, children) in interaction_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        let enabled = button.0.enabled_mut(&mut save_data.settings);
+        *enabled = !*enabled;
+        save_data.save();
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = button.0.label(&save_data.settings, &localization);
+            }
+        }
+    }
+}
+
+/// Handles switching to the next language. The screen is rebuilt right away so all of its text is in the new language.
+fn language_button_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut save_data: ResMut<SaveData>,
+    mut localization: ResMut<Localization>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<LanguageButton>)>,
+    screen_query: Query<Entity, With<SettingsComponent>>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked);
+    if !clicked {
+        return;
+    }
+
+    save_data.settings.language = save_data.settings.language.next();
+    save_data.save();
+    *localization = Localization::new(save_data.settings.language);
+
+    respawn_settings_screen(
+        &mut commands,
+        &asset_server,
+        screen_query,
+        &save_data,
+        &localization,
+    );
+}
+
+/// Handles interactions with the controls button.
+fn controls_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ControlsButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Controls).unwrap();
+        }
+    }
+}
+
+/// Handles interactions with the back button.
+fn settings_back_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsBackButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Menu).unwrap();
+        }
+    }
+}
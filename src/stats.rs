@@ -0,0 +1,344 @@
+use crate::*;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(RunPlayTime(0.0))
+            .add_system_set(SystemSet::on_enter(GameState::Stats).with_system(stats_setup))
+            .add_system_set(
+                SystemSet::on_exit(GameState::Stats)
+                    .with_system(despawn_components_system::<StatsComponent>),
+            )
+            .add_system_set(SystemSet::on_enter(GameState::Game).with_system(run_start_system))
+            .add_system_set(
+                SystemSet::on_update(GameState::Game)
+                    .with_run_criteria(game_running)
+                    .with_system(play_time_system),
+            )
+            .add_system_set(SystemSet::on_exit(GameState::Game).with_system(run_end_system))
+            .add_system(stats_back_button_system)
+            .add_system(export_button_system)
+            .add_system(import_button_system);
+    }
+}
+
+#[derive(Component)]
+struct StatsComponent;
+
+#[derive(Component)]
+struct StatsBackButton;
+
+#[derive(Component)]
+struct ExportButton;
+
+#[derive(Component)]
+struct ImportButton;
+
+#[derive(Component)]
+struct StatsText;
+
+/// Shows how the last export or import went
+#[derive(Component)]
+struct TransferText;
+
+/// How many seconds have been spent in the current run so far
+pub struct RunPlayTime(pub f64);
+
+/// Sets up the stats screen.
+fn stats_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+) {
+    let font = asset_server.load(MAIN_FONT);
+    let ui_scale = save_data.settings.ui_scale();
+
+    let transfer_hint = match transfer_path() {
+        Some(path) => localization.localize_with(
+            "stats.transfer_hint",
+            &[("path", path.display().to_string())],
+        ),
+        None => localization
+            .localize("stats.transfer_unsupported_hint")
+            .to_string(),
+    };
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .insert(StatsComponent)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    localization.localize("stats.title"),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 70.0 * ui_scale,
+                        color: Color::WHITE,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                style: Style {
+                    margin: Rect::all(Val::Px(15.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        stats_text(&save_data.stats, &localization),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 30.0 * ui_scale,
+                            color: Color::WHITE,
+                        },
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        },
+                    ),
+                    style: Style {
+                        margin: Rect::all(Val::Px(15.0)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(StatsText);
+
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        transfer_hint,
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 18.0 * ui_scale,
+                            color: Color::GRAY,
+                        },
+                        TextAlignment {
+                            horizontal: HorizontalAlign::Center,
+                            ..Default::default()
+                        },
+                    ),
+                    style: Style {
+                        margin: Rect::all(Val::Px(5.0)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(TransferText);
+
+            parent
+                .spawn_bundle(NodeBundle {
+                    color: UiColor(Color::NONE),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    spawn_button(
+                        parent,
+                        font.clone(),
+                        ui_scale,
+                        localization.localize("common.back"),
+                        StatsBackButton,
+                    );
+                    spawn_button(
+                        parent,
+                        font.clone(),
+                        ui_scale,
+                        localization.localize("stats.export"),
+                        ExportButton,
+                    );
+                    spawn_button(
+                        parent,
+                        font.clone(),
+                        ui_scale,
+                        localization.localize("stats.import"),
+                        ImportButton,
+                    );
+                });
+        });
+}
+
+/// The lines of lifetime stats to show
+fn stats_text(stats: &Stats, localization: &Localization) -> String {
+    let best_wake_time = match stats.best_wake_time {
+        Some(time) => time.to_string(),
+        None => localization.localize("stats.none_yet").to_string(),
+    };
+    [
+        localization.localize_with("stats.runs", &[("count", stats.total_runs.to_string())]),
+        localization.localize_with(
+            "stats.total_snoozes",
+            &[("count", stats.total_snoozes.to_string())],
+        ),
+        localization.localize_with(
+            "stats.most_snoozes",
+            &[("count", stats.most_snoozes.to_string())],
+        ),
+        localization.localize_with("stats.wins", &[("count", stats.wins.to_string())]),
+        localization.localize_with("stats.latest_wake_up", &[("time", best_wake_time)]),
+        localization.localize_with(
+            "stats.time_played",
+            &[("time", format_duration(stats.seconds_played))],
+        ),
+    ]
+    .join("\n")
+}
+
+/// Why an export or import didn't work, for showing to the player
+fn transfer_error_text(error: &TransferError, localization: &Localization) -> String {
+    match error {
+        TransferError::Unsupported => localization
+            .localize("stats.transfer_unsupported")
+            .to_string(),
+        TransferError::Io(path, e) => localization.localize_with(
+            "stats.transfer_io",
+            &[
+                ("path", path.display().to_string()),
+                ("error", e.to_string()),
+            ],
+        ),
+        TransferError::Corrupt(e) => {
+            localization.localize_with("stats.transfer_corrupt", &[("error", e.to_string())])
+        }
+        TransferError::TooNew(version) => localization.localize_with(
+            "stats.transfer_too_new",
+            &[
+                ("version", version.to_string()),
+                ("current", SAVE_VERSION.to_string()),
+            ],
+        ),
+    }
+}
+
+/// Formats a number of seconds like "1h 2m 3s", leaving off the hours if there aren't any
+fn format_duration(seconds: f64) -> String {
+    let total_seconds = seconds as u64;
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+    if hours > 0 {
+        format!("{hours}h {minutes}m {seconds}s")
+    } else {
+        format!("{minutes}m {seconds}s")
+    }
+}
+
+/// Handles interactions with the back button.
+fn stats_back_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<StatsBackButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Menu).unwrap();
+        }
+    }
+}
+
+/// Handles interactions with the export button.
+fn export_button_system(
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ExportButton>)>,
+    mut transfer_text_query: Query<&mut Text, With<TransferText>>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        let message = match save_data.export() {
+            Ok(path) => localization
+                .localize_with("stats.exported", &[("path", path.display().to_string())]),
+            Err(e) => {
+                warn!("couldn't export save: {e}");
+                localization.localize_with(
+                    "stats.export_failed",
+                    &[("error", transfer_error_text(&e, &localization))],
+                )
+            }
+        };
+        for mut text in transfer_text_query.iter_mut() {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+/// Handles interactions with the import button. The current save is only replaced if the imported one checks out.
+fn import_button_system(
+    mut save_data: ResMut<SaveData>,
+    localization: Res<Localization>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ImportButton>)>,
+    mut stats_text_query: Query<&mut Text, (With<StatsText>, Without<TransferText>)>,
+    mut transfer_text_query: Query<&mut Text, (With<TransferText>, Without<StatsText>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        let message = match SaveData::import() {
+            Ok(imported) => {
+                *save_data = imported;
+                save_data.save();
+                for mut text in stats_text_query.iter_mut() {
+                    text.sections[0].value = stats_text(&save_data.stats, &localization);
+                }
+                localization.localize("stats.imported").to_string()
+            }
+            Err(e) => {
+                warn!("couldn't import save: {e}");
+                localization.localize_with(
+                    "stats.import_failed",
+                    &[("error", transfer_error_text(&e, &localization))],
+                )
+            }
+        };
+        for mut text in transfer_text_query.iter_mut() {
+            text.sections[0].value = message.clone();
+        }
+    }
+}
+
+/// Counts the start of a run
+fn run_start_system(mut save_data: ResMut<SaveData>, mut run_play_time: ResMut<RunPlayTime>) {
+    save_data.stats.total_runs += 1;
+    run_play_time.0 = 0.0;
+}
+
+/// Keeps track of how long the current run has been going, not counting time spent paused.
+/// The clock stops as soon as the run is lost or won, so the death sequence doesn't count.
+fn play_time_system(
+    time: Res<Time>,
+    death_sequence: Res<DeathSequence>,
+    mut run_play_time: ResMut<RunPlayTime>,
+) {
+    if death_sequence.is_started() {
+        return;
+    }
+
+    run_play_time.0 += time.delta_seconds_f64();
+}
+
+/// Adds the time spent in a run to the lifetime total once it's over
+fn run_end_system(mut save_data: ResMut<SaveData>, mut run_play_time: ResMut<RunPlayTime>) {
+    save_data.stats.seconds_played += run_play_time.0;
+    run_play_time.0 = 0.0;
+    save_data.save();
+}
@@ -0,0 +1,360 @@
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+const LOCKED_TEXT_COLOR: Color = Color::GRAY;
+
+pub struct UnlocksPlugin;
+
+impl Plugin for UnlocksPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(GameState::Unlocks).with_system(unlocks_setup))
+            .add_system_set(
+                SystemSet::on_exit(GameState::Unlocks)
+                    .with_system(despawn_components_system::<UnlocksComponent>),
+            )
+            .add_system(unlock_button_system)
+            .add_system(unlocks_back_button_system);
+    }
+}
+
+#[derive(Component)]
+struct UnlocksComponent;
+
+#[derive(Component)]
+struct UnlocksBackButton;
+
+/// A button for picking a cosmetic, which only does anything once it's unlocked
+#[derive(Component, Clone, Copy)]
+enum UnlockButton {
+    PhoneSkin(PhoneSkin),
+    AlarmTone(AlarmTone),
+}
+
+impl UnlockButton {
+    /// How many times the player has to get up to unlock this
+    fn required_wins(&self) -> u32 {
+        match self {
+            UnlockButton::PhoneSkin(skin) => skin.required_wins(),
+            UnlockButton::AlarmTone(tone) => tone.required_wins(),
+        }
+    }
+
+    /// Whether this is what's currently picked
+    fn is_selected(&self, settings: &Settings) -> bool {
+        match self {
+            UnlockButton::PhoneSkin(skin) => settings.phone_skin == *skin,
+            UnlockButton::AlarmTone(tone) => settings.alarm_tone == *tone,
+        }
+    }
+
+    /// The label for this button, given how the player is doing
+    fn label(&self, save_data: &SaveData, localization: &Localization) -> String {
+        let name = match self {
+            UnlockButton::PhoneSkin(skin) => localization.localize_with(
+                "unlocks.phone",
+                &[("name", localization.localize(skin.name_key()).to_string())],
+            ),
+            UnlockButton::AlarmTone(tone) => localization.localize_with(
+                "unlocks.alarm",
+                &[("name", localization.localize(tone.name_key()).to_string())],
+            ),
+        };
+
+        if !save_data.is_unlocked(self.required_wins()) {
+            localization.localize_with(
+                "unlocks.locked",
+                &[("name", name), ("wins", self.required_wins().to_string())],
+            )
+        } else if self.is_selected(&save_data.settings) {
+            format!("> {name} <")
+        } else {
+            name
+        }
+    }
+
+    /// The color of the label for this button, given how the player is doing
+    fn label_color(&self, save_data: &SaveData) -> Color {
+        if save_data.is_unlocked(self.required_wins()) {
+            Color::SEA_GREEN
+        } else {
+            LOCKED_TEXT_COLOR
+        }
+    }
+}
+
+/// What the phone looks like
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum PhoneSkin {
+    Classic,
+    Midnight,
+    Sunrise,
+}
+
+impl Default for PhoneSkin {
+    fn default() -> Self {
+        PhoneSkin::Classic
+    }
+}
+
+impl PhoneSkin {
+    pub const ALL: [PhoneSkin; 3] = [PhoneSkin::Classic, PhoneSkin::Midnight, PhoneSkin::Sunrise];
+
+    /// How many times the player has to get up to unlock this skin
+    pub fn required_wins(&self) -> u32 {
+        match self {
+            PhoneSkin::Classic => 0,
+            PhoneSkin::Midnight => 3,
+            PhoneSkin::Sunrise => 6,
+        }
+    }
+
+    /// The color the phone image is tinted with
+    pub fn tint(&self) -> Color {
+        match self {
+            PhoneSkin::Classic => Color::WHITE,
+            PhoneSkin::Midnight => Color::rgb(0.45, 0.5, 0.85),
+            PhoneSkin::Sunrise => Color::rgb(1.0, 0.75, 0.55),
+        }
+    }
+
+    /// The key for the name of this skin, for showing to the player
+    fn name_key(&self) -> &'static str {
+        match self {
+            PhoneSkin::Classic => "unlocks.skin_classic",
+            PhoneSkin::Midnight => "unlocks.skin_midnight",
+            PhoneSkin::Sunrise => "unlocks.skin_sunrise",
+        }
+    }
+}
+
+/// What the alarm sounds like
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub enum AlarmTone {
+    Classic,
+    Chirpy,
+    Groggy,
+}
+
+impl Default for AlarmTone {
+    fn default() -> Self {
+        AlarmTone::Classic
+    }
+}
+
+impl AlarmTone {
+    pub const ALL: [AlarmTone; 3] = [AlarmTone::Classic, AlarmTone::Chirpy, AlarmTone::Groggy];
+
+    /// How many times the player has to get up to unlock this tone
+    pub fn required_wins(&self) -> u32 {
+        match self {
+            AlarmTone::Classic => 0,
+            AlarmTone::Chirpy => 2,
+            AlarmTone::Groggy => 5,
+        }
+    }
+
+    /// How fast the alarm sound is played, relative to normal
+    pub fn playback_rate(&self) -> f32 {
+        match self {
+            AlarmTone::Classic => 1.0,
+            AlarmTone::Chirpy => 1.3,
+            AlarmTone::Groggy => 0.75,
+        }
+    }
+
+    /// The key for the name of this tone, for showing to the player
+    fn name_key(&self) -> &'static str {
+        match self {
+            AlarmTone::Classic => "unlocks.tone_classic",
+            AlarmTone::Chirpy => "unlocks.tone_chirpy",
+            AlarmTone::Groggy => "unlocks.tone_groggy",
+        }
+    }
+}
+
+/// Spawns a button for picking the provided cosmetic
+fn spawn_unlock_button(
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+    ui_scale: f32,
+    save_data: &SaveData,
+    localization: &Localization,
+    button: UnlockButton,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Px(450.0 * ui_scale), Val::Px(50.0 * ui_scale)),
+                margin: Rect::all(Val::Px(5.0 * ui_scale)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .insert(button)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    button.label(save_data, localization),
+                    TextStyle {
+                        font,
+                        font_size: 25.0 * ui_scale,
+                        color: button.label_color(save_data),
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                ..Default::default()
+            });
+        });
+}
+
+/// Sets up the unlocks screen.
+fn unlocks_setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    save_data: Res<SaveData>,
+    localization: Res<Localization>,
+) {
+    let font = asset_server.load(MAIN_FONT);
+    let ui_scale = save_data.settings.ui_scale();
+
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: UiColor(Color::NONE),
+            ..Default::default()
+        })
+        .insert(UnlocksComponent)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    localization.localize("unlocks.title"),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 70.0 * ui_scale,
+                        color: Color::WHITE,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                style: Style {
+                    margin: Rect::all(Val::Px(15.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    localization.localize_with(
+                        "unlocks.wins",
+                        &[("count", save_data.stats.wins.to_string())],
+                    ),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 25.0 * ui_scale,
+                        color: Color::WHITE,
+                    },
+                    TextAlignment {
+                        horizontal: HorizontalAlign::Center,
+                        ..Default::default()
+                    },
+                ),
+                style: Style {
+                    margin: Rect::all(Val::Px(15.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+
+            for skin in PhoneSkin::ALL {
+                spawn_unlock_button(
+                    parent,
+                    font.clone(),
+                    ui_scale,
+                    &save_data,
+                    &localization,
+                    UnlockButton::PhoneSkin(skin),
+                );
+            }
+            for tone in AlarmTone::ALL {
+                spawn_unlock_button(
+                    parent,
+                    font.clone(),
+                    ui_scale,
+                    &save_data,
+                    &localization,
+                    UnlockButton::AlarmTone(tone),
+                );
+            }
+
+            spawn_button(
+                parent,
+                font.clone(),
+                ui_scale,
+                localization.localize("common.back"),
+                UnlocksBackButton,
+            );
+        });
+}
+
+/// Handles picking an unlocked cosmetic. Locked ones can't be picked.
+fn unlock_button_system(
+    mut save_data: ResMut<SaveData>,
+    localization: Res<Localization>,
+    interaction_query: Query<(&Interaction, &UnlockButton), Changed<Interaction>>,
+    button_query: Query<(&UnlockButton, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let mut picked = false;
+    for (interaction, button) in interaction_query.iter() {
+        if *interaction != Interaction::Clicked || !save_data.is_unlocked(button.required_wins()) {
+            continue;
+        }
+
+        match button {
+            UnlockButton::PhoneSkin(skin) => save_data.settings.phone_skin = *skin,
+            UnlockButton::AlarmTone(tone) => save_data.settings.alarm_tone = *tone,
+        }
+        picked = true;
+    }
+
+    if !picked {
+        return;
+    }
+
+    save_data.save();
+    for (button, children) in button_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = button.label(&save_data, &localization);
+            }
+        }
+    }
+}
+
+/// Handles interactions with the back button.
+fn unlocks_back_button_system(
+    mut game_state: ResMut<State<GameState>>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<UnlocksBackButton>)>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Clicked {
+            game_state.set(GameState::Menu).unwrap();
+        }
+    }
+}